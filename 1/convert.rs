@@ -0,0 +1,17 @@
+use std::path::Path;
+use std::env;
+
+mod inventory;
+
+use inventory::Inventory;
+
+// Converts between inventory formats, picked by extension: .csv, .json, anything else is the puzzle format
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let input = Path::new(args.get(1).expect("No input filename provided."));
+    let output = Path::new(args.get(2).expect("No output filename provided."));
+
+    let inventory = Inventory::load(&input).expect("Couldn't load inventory.");
+    inventory.save(&output).expect("Couldn't save inventory.");
+    println!("Converted {} elves.", inventory.elves.len());
+}
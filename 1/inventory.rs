@@ -0,0 +1,168 @@
+// Elf inventories in the three formats we deal with:
+// - puzzle: blank-line-separated groups of integers, elves numbered from 1 in order
+// - csv: one `elf,calories` row per item, rows of the same elf need not be adjacent
+// - json: [{"elf": "1", "calories": [1000, 2000]}, ...]
+#![allow(dead_code)]
+
+#[path = "../common/json.rs"]
+mod json;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use self::json::Json;
+
+const CSV_HEADER: &str = "elf,calories";
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Format { Puzzle, Csv, Json }
+
+impl Format {
+    // Anything that isn't .csv or .json is treated as the puzzle format
+    pub fn from_path(path: &Path) -> Format {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("csv") => Format::Csv,
+            Some("json") => Format::Json,
+            _ => Format::Puzzle,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Elf {
+    pub id: String,
+    pub items: Vec<u32>,
+}
+
+impl Elf {
+    pub fn total(&self) -> u32 {
+        self.items.iter().sum()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn load(path: &Path) -> Result<Inventory, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("Couldn't read file: {e}"))?;
+        Inventory::parse(&contents, Format::from_path(path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.write(Format::from_path(path))).map_err(|e| format!("Couldn't write file: {e}"))
+    }
+
+    pub fn parse(contents: &str, format: Format) -> Result<Inventory, String> {
+        match format {
+            Format::Puzzle => Inventory::parse_puzzle(contents),
+            Format::Csv => Inventory::parse_csv(contents),
+            Format::Json => Inventory::parse_json(contents),
+        }
+    }
+
+    pub fn write(&self, format: Format) -> String {
+        match format {
+            Format::Puzzle => self.to_puzzle(),
+            Format::Csv => self.to_csv(),
+            Format::Json => self.to_json(),
+        }
+    }
+
+    // Sums of calories per elf, largest first
+    pub fn top_totals(&self, n: usize) -> Vec<(&str, u32)> {
+        let mut totals: Vec<(&str, u32)> = self.elves.iter().map(|e| (e.id.as_str(), e.total())).collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1));
+        totals.truncate(n);
+        totals
+    }
+
+    fn parse_puzzle(contents: &str) -> Result<Inventory, String> {
+        let mut elves = Vec::new();
+        for list in contents.split("\n\n") {
+            let mut items = Vec::new();
+            for item in list.lines().map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                items.push(item.parse().map_err(|_| format!("Invalid calorie count: {item}"))?);
+            }
+            if !items.is_empty() {
+                elves.push(Elf { id: (elves.len() + 1).to_string(), items: items });
+            }
+        }
+        Ok(Inventory { elves: elves })
+    }
+
+    fn parse_csv(contents: &str) -> Result<Inventory, String> {
+        let mut inventory = Inventory { elves: Vec::new() };
+        // Elf id -> index in `elves`, which keeps the order of first appearance
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (i == 0 && line == CSV_HEADER) {
+                continue;
+            }
+            // Split on the last comma so that elf ids may contain commas
+            let (id, calories) = line.rsplit_once(",").ok_or(format!("Line {}: expected `elf,calories`.", i + 1))?;
+            let calories: u32 = calories.trim().parse()
+                .map_err(|_| format!("Line {}: invalid calorie count.", i + 1))?;
+            let id = id.trim();
+            match index.get(id) {
+                Some(&k) => inventory.elves[k].items.push(calories),
+                None => {
+                    index.insert(String::from(id), inventory.elves.len());
+                    inventory.elves.push(Elf { id: String::from(id), items: vec![calories] });
+                }
+            }
+        }
+        Ok(inventory)
+    }
+
+    fn parse_json(contents: &str) -> Result<Inventory, String> {
+        let root = Json::parse(contents)?;
+        let mut inventory = Inventory { elves: Vec::new() };
+        for entry in root.as_array().ok_or("Expected a list of elves.")? {
+            let id = match entry.get("elf") {
+                Some(Json::Str(s)) => s.clone(),
+                Some(Json::Number(x)) => x.to_string(),
+                _ => return Err(String::from("Elf without an \"elf\" id.")),
+            };
+            let calories = entry.get("calories").and_then(|x| x.as_array())
+                .ok_or(format!("Elf {id} has no \"calories\" list."))?;
+            let mut items = Vec::new();
+            for x in calories {
+                let x = x.as_i64().filter(|x| *x >= 0 && *x <= u32::MAX as i64)
+                    .ok_or(format!("Elf {id} has an invalid calorie count."))?;
+                items.push(x as u32);
+            }
+            inventory.elves.push(Elf { id: id, items: items });
+        }
+        Ok(inventory)
+    }
+
+    fn to_puzzle(&self) -> String {
+        let lists: Vec<String> = self.elves.iter()
+            .map(|e| e.items.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("\n"))
+            .collect();
+        lists.join("\n\n") + "\n"
+    }
+
+    fn to_csv(&self) -> String {
+        let mut out = String::from(CSV_HEADER);
+        out.push('\n');
+        for elf in &self.elves {
+            for item in &elf.items {
+                out.push_str(&format!("{},{}\n", elf.id, item));
+            }
+        }
+        out
+    }
+
+    fn to_json(&self) -> String {
+        let elves = self.elves.iter().map(|e| Json::Object(vec![
+            (String::from("elf"), Json::Str(e.id.clone())),
+            (String::from("calories"), Json::Array(e.items.iter().map(|x| Json::Number(*x as i64)).collect())),
+        ])).collect();
+        Json::Array(elves).pretty() + "\n"
+    }
+}
@@ -1,25 +1,15 @@
-use std::fs::File;
 use std::path::Path;
-use std::io::Read;
+use std::env;
+
+mod inventory;
+
+use inventory::Inventory;
 
 fn main() {
-    let path = Path::new("input1.txt");
-    let mut file = File::open(&path).expect("Couldn't open file.");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Couldn't read file.");
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).map(|x| x.as_str()).unwrap_or("input1.txt"));
+    let inventory = Inventory::load(&path).expect("Couldn't load inventory.");
 
-    let mut max_sum = -1;
-    let elf_lists = contents.split("\n\n");
-    for list in elf_lists {
-        let elf_items = list.split("\n");
-        let mut sum = 0;
-        for item in elf_items {
-            let integer_item: i32 = item.parse().unwrap_or(0);
-            sum += integer_item;
-        }
-        if sum > max_sum {
-            max_sum = sum
-        }
-    }
+    let max_sum = inventory.elves.iter().map(|e| e.total()).max().unwrap_or(0);
     println!("{max_sum}");
 }
@@ -1,25 +1,22 @@
-use std::fs::File;
 use std::path::Path;
-use std::io::Read;
+use std::env;
 
+mod inventory;
+
+use inventory::Inventory;
+
+// Usage: main2 [inventory file (.txt, .csv or .json)] [N, defaults to 3]
 fn main() {
-    let path = Path::new("input1.txt");
-    let mut file = File::open(&path).expect("Couldn't open file.");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Couldn't read file.");
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).map(|x| x.as_str()).unwrap_or("input1.txt"));
+    let n: usize = args.get(2).map(|x| x.parse().expect("Invalid N.")).unwrap_or(3);
+    let inventory = Inventory::load(&path).expect("Couldn't load inventory.");
 
-    let mut sums: Vec<i32> = Vec::new();
-    let elf_lists = contents.split("\n\n");
-    for list in elf_lists {
-        let elf_items = list.split("\n");
-        let mut sum = 0;
-        for item in elf_items {
-            let integer_item: i32 = item.parse().unwrap_or(0);
-            sum += integer_item;
+    let top = inventory.top_totals(n);
+    if args.len() > 2 {
+        for (id, total) in &top {
+            println!("elf {id}: {total}");
         }
-        sums.push(sum);
     }
-
-    sums.sort_by(|a, b| b.cmp(a));
-    println!("{}", sums[0] + sums[1] + sums[2]);
+    println!("{}", top.iter().map(|(_, total)| total).sum::<u32>());
 }
@@ -0,0 +1,212 @@
+// The handheld's CPU: a single register X, instructions taking a configurable number of cycles,
+// and observers called during every cycle.
+#![allow(dead_code)]
+
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Result<Instruction, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            ["noop"] => Ok(Instruction::Noop),
+            ["addx", x] => x.parse().map(Instruction::Addx).map_err(|_| format!("invalid operand `{x}`.")),
+            ["addx"] | ["addx", _, ..] => Err(String::from("addx takes exactly one operand.")),
+            [op, ..] => Err(format!("unknown opcode `{op}`.")),
+            [] => Err(String::from("empty instruction.")),
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Noop => "noop",
+            Instruction::Addx(_) => "addx",
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::Addx(x) => write!(f, "addx {x}"),
+        }
+    }
+}
+
+pub fn parse_program(contents: &str) -> Result<Vec<Instruction>, String> {
+    contents.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| Instruction::parse(line).map_err(|e| format!("Line {}: {e}", i + 1)))
+        .collect()
+}
+
+// Prints the program back in its source form, one instruction per line with its address
+pub fn disassemble(program: &[Instruction]) -> String {
+    let mut out = String::new();
+    for (i, instr) in program.iter().enumerate() {
+        out.push_str(&format!("{i:4}  {instr}\n"));
+    }
+    out
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CycleCosts {
+    pub noop: u32,
+    pub addx: u32,
+}
+
+impl Default for CycleCosts {
+    fn default() -> CycleCosts {
+        CycleCosts { noop: 1, addx: 2 }
+    }
+}
+
+impl CycleCosts {
+    // Defaults overridden by `--cost-noop <n>` and `--cost-addx <n>`
+    pub fn from_args(args: &[String]) -> Result<CycleCosts, String> {
+        let mut costs = CycleCosts::default();
+        for (name, field) in [("--cost-noop", &mut costs.noop), ("--cost-addx", &mut costs.addx)] {
+            if let Some(i) = args.iter().position(|x| x == name) {
+                *field = args.get(i + 1).and_then(|x| x.parse().ok()).filter(|x| *x > 0)
+                    .ok_or(format!("{name} needs a positive number of cycles."))?;
+            }
+        }
+        Ok(costs)
+    }
+
+    pub fn of(&self, instr: &Instruction) -> u32 {
+        match instr {
+            Instruction::Noop => self.noop,
+            Instruction::Addx(_) => self.addx,
+        }
+    }
+}
+
+// State visible to observers *during* a cycle, i.e. before the current instruction completes
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CycleState {
+    // 1-based, as in the puzzle
+    pub cycle: u32,
+    pub x: i32,
+    // Index of the instruction being executed
+    pub pc: usize,
+}
+
+pub trait Observer {
+    fn on_cycle(&mut self, state: &CycleState);
+}
+
+pub struct Cpu {
+    pub program: Vec<Instruction>,
+    pub costs: CycleCosts,
+    pub x: i32,
+    // Index of the next instruction to execute
+    pub pc: usize,
+    // Cycles completed so far
+    pub cycle: u32,
+    // Cycles already spent on the current instruction
+    progress: u32,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>, costs: CycleCosts) -> Cpu {
+        Cpu { program: program, costs: costs, x: 1, pc: 0, cycle: 0, progress: 0 }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    // True if no cycle has been spent on the instruction at `pc` yet
+    pub fn at_instruction_start(&self) -> bool {
+        self.progress == 0
+    }
+
+    pub fn current(&self) -> Option<Instruction> {
+        self.program.get(self.pc).copied()
+    }
+
+    pub fn state(&self) -> CycleState {
+        CycleState { cycle: self.cycle + 1, x: self.x, pc: self.pc }
+    }
+
+    // Runs one cycle: observers see the state during the cycle, then the current instruction
+    // completes if this was its last cycle. Returns false if the program has already ended.
+    pub fn tick(&mut self, observers: &mut [&mut dyn Observer]) -> bool {
+        if self.halted() {
+            return false;
+        }
+        let state = self.state();
+        for o in observers.iter_mut() {
+            o.on_cycle(&state);
+        }
+        self.cycle += 1;
+        self.progress += 1;
+
+        let instr = self.program[self.pc];
+        if self.progress >= self.costs.of(&instr) {
+            if let Instruction::Addx(v) = instr {
+                self.x += v;
+            }
+            self.pc += 1;
+            self.progress = 0;
+        }
+        true
+    }
+
+    pub fn run(&mut self, observers: &mut [&mut dyn Observer]) {
+        while self.tick(observers) {}
+    }
+}
+
+// Sum of cycle * X over the given cycles
+pub struct SignalStrength {
+    pub checkpoints: Vec<u32>,
+    pub total: i64,
+}
+
+impl SignalStrength {
+    pub fn new(checkpoints: Vec<u32>) -> SignalStrength {
+        SignalStrength { checkpoints: checkpoints, total: 0 }
+    }
+}
+
+impl Observer for SignalStrength {
+    fn on_cycle(&mut self, state: &CycleState) {
+        if self.checkpoints.contains(&state.cycle) {
+            self.total += state.cycle as i64 * state.x as i64;
+        }
+    }
+}
+
+// The CRT draws one pixel per cycle, lit if the 3 pixel wide sprite centered on X covers it
+pub struct Crt {
+    pub grid: Vec<Vec<char>>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Crt {
+        Crt { grid: vec![vec!['.'; width]; height] }
+    }
+
+    pub fn render(&self) -> String {
+        self.grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+    }
+}
+
+impl Observer for Crt {
+    fn on_cycle(&mut self, state: &CycleState) {
+        let width = self.grid[0].len();
+        let pos = state.cycle as usize - 1;
+        let (row, col) = (pos / width, pos % width);
+        if row < self.grid.len() && (state.x - col as i32).abs() <= 1 {
+            self.grid[row][col] = '#';
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::fs;
+use std::path::Path;
+use std::env;
+use std::io::{self, BufRead, Write};
+
+mod cpu;
+
+use cpu::{Cpu, CycleCosts, Crt};
+
+// Usage: debug <file> [--cost-noop <n>] [--cost-addx <n>] [--live]
+// Steps through the program with commands read from stdin, `help` lists them. With --live the
+// CRT drawn so far is shown whenever execution stops.
+
+const HELP: &str = "\
+step [n]          run n cycles (1 by default)
+next              run until the current instruction completes
+continue          run until a breakpoint, a watchpoint or the end of the program
+break cycle <n>   stop before cycle n
+break pc <n>      stop before instruction n starts
+delete <k>        remove breakpoint k
+info              list breakpoints and watchpoints
+watch             stop whenever X changes (toggle)
+print             show the registers
+list              show the instructions around pc
+crt               show the CRT drawn so far, `@` being the next pixel
+live              show the CRT whenever execution stops (toggle)
+quit
+An empty line repeats the previous command, commands can be shortened to their first letter.";
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Breakpoint {
+    Cycle(u32),
+    Pc(usize),
+}
+
+struct Debugger {
+    cpu: Cpu,
+    crt: Crt,
+    breakpoints: Vec<Breakpoint>,
+    watch_x: bool,
+    // X when the watchpoint was last checked
+    last_x: i32,
+    // Cycle of the state the last breakpoint or watchpoint stopped at
+    stopped_at: Option<u32>,
+    live: bool,
+}
+
+impl Debugger {
+    // Change of X since the watchpoint was last checked, if it's set
+    fn check_watch(&mut self) -> Option<String> {
+        let x = self.last_x;
+        self.last_x = self.cpu.x;
+        if self.watch_x && self.cpu.x != x {
+            Some(format!("X changed from {x} to {}", self.cpu.x))
+        } else {
+            None
+        }
+    }
+
+    // Why execution should stop in the current state, before its cycle runs, if it should. A
+    // breakpoint doesn't fire again in the state execution last stopped at, so resuming from
+    // it moves on.
+    fn check_stop(&mut self) -> Option<String> {
+        let cycle = self.cpu.state().cycle;
+        let mut reason = self.check_watch();
+        if reason.is_none() && self.stopped_at != Some(cycle) {
+            for (k, b) in self.breakpoints.iter().enumerate() {
+                let hit = match b {
+                    Breakpoint::Cycle(c) => cycle == *c,
+                    Breakpoint::Pc(pc) => self.cpu.pc == *pc && self.cpu.at_instruction_start(),
+                };
+                if hit {
+                    reason = Some(format!("breakpoint {k} ({})", describe(b)));
+                    break;
+                }
+            }
+        }
+        if reason.is_some() {
+            self.stopped_at = Some(cycle);
+        }
+        reason
+    }
+
+    // Runs cycles until `until` holds after one of them, returning why execution stopped
+    // earlier if it did
+    fn run(&mut self, until: impl Fn(&Cpu) -> bool) -> Option<String> {
+        loop {
+            if self.cpu.halted() {
+                // The last instruction may still have changed X
+                return match self.check_watch() {
+                    Some(change) => Some(format!("program ended, {change}")),
+                    None => Some(String::from("program ended")),
+                };
+            }
+            if let Some(reason) = self.check_stop() {
+                return Some(reason);
+            }
+            self.cpu.tick(&mut [&mut self.crt]);
+            if until(&self.cpu) {
+                // Report a breakpoint we just reached, which then won't stop the next command
+                return if self.cpu.halted() { self.check_watch() } else { self.check_stop() };
+            }
+        }
+    }
+
+    fn print_state(&self) {
+        let state = self.cpu.state();
+        // Once halted there's no upcoming cycle, show the last one that ran instead
+        let cycle = if self.cpu.halted() { self.cpu.cycle } else { state.cycle };
+        let instr = self.cpu.current().map_or(String::from("(halted)"), |i| i.to_string());
+        println!("cycle {:4}  X = {:4}  pc = {:4}  {instr}", cycle, state.x, state.pc);
+    }
+
+    fn print_crt(&self) {
+        let width = self.crt.grid[0].len();
+        let pos = self.cpu.cycle as usize;
+        for (i, row) in self.crt.grid.iter().enumerate() {
+            let line: String = row.iter().enumerate()
+                .map(|(j, ch)| if i * width + j == pos && !self.cpu.halted() { '@' } else { *ch })
+                .collect();
+            println!("{line}");
+        }
+    }
+
+    fn list(&self) {
+        let start = self.cpu.pc.saturating_sub(3);
+        let end = (self.cpu.pc + 4).min(self.cpu.program.len());
+        for addr in start..end {
+            let marker = if addr == self.cpu.pc { "=>" } else { "  " };
+            println!("{marker}{addr:4}  {}", self.cpu.program[addr]);
+        }
+    }
+
+    // Returns false when the session is over
+    fn execute(&mut self, words: &[&str]) -> Result<bool, String> {
+        let number = |i: usize| -> Result<u64, String> {
+            let word = words.get(i).ok_or(String::from("missing number."))?;
+            word.parse().map_err(|_| format!("invalid number `{word}`."))
+        };
+        let stop = match words[0] {
+            "s" | "step" => {
+                let n = if words.len() > 1 { number(1)? } else { 1 };
+                let mut reason = None;
+                for _ in 0..n {
+                    reason = self.run(|_| true);
+                    if reason.is_some() {
+                        break;
+                    }
+                }
+                Some(reason)
+            }
+            "n" | "next" => Some(self.run(|cpu| cpu.at_instruction_start())),
+            "c" | "continue" => Some(self.run(|_| false)),
+            "b" | "break" => {
+                let b = match words.get(1) {
+                    Some(&"cycle") => Breakpoint::Cycle(number(2)? as u32),
+                    Some(&"pc") => Breakpoint::Pc(number(2)? as usize),
+                    _ => return Err(String::from("usage: break cycle <n> | break pc <n>")),
+                };
+                println!("breakpoint {} ({})", self.breakpoints.len(), describe(&b));
+                self.breakpoints.push(b);
+                None
+            }
+            "d" | "delete" => {
+                let k = number(1)? as usize;
+                if k >= self.breakpoints.len() {
+                    return Err(format!("no breakpoint {k}."));
+                }
+                self.breakpoints.remove(k);
+                None
+            }
+            "i" | "info" => {
+                for (k, b) in self.breakpoints.iter().enumerate() {
+                    println!("breakpoint {k} ({})", describe(b));
+                }
+                if self.watch_x {
+                    println!("watching X");
+                }
+                None
+            }
+            "w" | "watch" => {
+                self.watch_x = !self.watch_x;
+                self.last_x = self.cpu.x;
+                println!("{} X", if self.watch_x { "watching" } else { "not watching" });
+                None
+            }
+            "l" | "list" => {
+                self.list();
+                None
+            }
+            "p" | "print" => {
+                self.print_state();
+                None
+            }
+            "crt" => {
+                self.print_crt();
+                None
+            }
+            "live" => {
+                self.live = !self.live;
+                println!("live CRT {}", if self.live { "on" } else { "off" });
+                None
+            }
+            "h" | "help" => {
+                println!("{HELP}");
+                None
+            }
+            "q" | "quit" => return Ok(false),
+            other => return Err(format!("unknown command `{other}`, try `help`.")),
+        };
+        // Execution commands report where they stopped
+        if let Some(reason) = stop {
+            if let Some(reason) = reason {
+                println!("stopped: {reason}");
+            }
+            self.print_state();
+            if self.live {
+                self.print_crt();
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn describe(b: &Breakpoint) -> String {
+    match b {
+        Breakpoint::Cycle(c) => format!("cycle {c}"),
+        Breakpoint::Pc(pc) => format!("pc {pc}"),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).expect("No filename provided."));
+    let contents = fs::read_to_string(&path).expect("Couldn't read file.");
+    let program = cpu::parse_program(&contents).expect("Invalid program.");
+    let costs = CycleCosts::from_args(&args).expect("Invalid cycle costs.");
+
+    let mut debugger = Debugger {
+        cpu: Cpu::new(program, costs),
+        crt: Crt::new(40, 6),
+        breakpoints: Vec::new(),
+        watch_x: false,
+        last_x: 1,
+        stopped_at: None,
+        live: args.contains(&String::from("--live")),
+    };
+    debugger.print_state();
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut previous = String::new();
+    loop {
+        print!("(debug) ");
+        io::stdout().flush().expect("Couldn't write to stdout.");
+        let line = match lines.next() {
+            Some(line) => line.expect("Couldn't read from stdin."),
+            None => break,
+        };
+        let line = if line.trim().is_empty() { previous.clone() } else { line };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        match debugger.execute(&words) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("{e}"),
+        }
+        previous = line.clone();
+    }
+}
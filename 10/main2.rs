@@ -1,47 +1,40 @@
-use std::fs::File;
+use std::fs;
 use std::path::Path;
 use std::env;
-use std::io::{BufReader, BufRead};
 
-type Grid = Vec<Vec<char>>;
+mod cpu;
+mod ocr;
 
-fn draw_pixel(grid: &mut Grid, counter: i32, register: i32) {
-    let x = counter as usize / grid[0].len();
-    let y = counter as usize % grid[0].len();
-    if register == y as i32 || register == y as i32 - 1 || register == y as i32 + 1 {
-        grid[x][y] = '#';
-    }
-}
+use cpu::{Cpu, CycleCosts, Crt};
 
+// Usage: main2 <file> [--cost-noop <n>] [--cost-addx <n>] [--disassemble] [--text]
+// Prints the CRT followed by the letters read from it, or only the letters with --text. Exits
+// with 1 if some glyph isn't recognized.
 fn main() {
     let args: Vec<String> = env::args().collect();
     let path = Path::new(args.get(1).expect("No filename provided."));
-    let file = File::open(&path).expect("Couldn't open file.");
-    let lines = BufReader::new(file).lines();
-
-    let mut counter: i32 = 0;
-    let mut register: i32 = 1;
-    let mut grid: Grid = vec![vec!['.'; 40]; 6];
-    for line_res in lines {
-        if let Ok(line) = line_res {
-            draw_pixel(&mut grid, counter, register);
-            counter += 1;
-
-            if line != "noop" {
-                draw_pixel(&mut grid, counter, register);
-                counter += 1;
-
-                let (_, x) = line.split_once(" ").unwrap();
-                let x: i32 = x.parse().unwrap();
-                register += x;
-            }
-        }
+    let contents = fs::read_to_string(&path).expect("Couldn't read file.");
+    let program = cpu::parse_program(&contents).expect("Invalid program.");
+    if args.contains(&String::from("--disassemble")) {
+        print!("{}", cpu::disassemble(&program));
+        return;
     }
 
-    for row in &grid {
-        for y in row {
-            print!("{y}");
+    let mut cpu = Cpu::new(program, CycleCosts::from_args(&args).expect("Invalid cycle costs."));
+    let mut crt = Crt::new(40, 6);
+    cpu.run(&mut [&mut crt]);
+    let letters = ocr::recognize(&crt.grid).expect("Couldn't read the CRT.");
+    if !args.contains(&String::from("--text")) {
+        print!("{}", crt.render());
+    }
+    println!("{}", letters.text);
+    for glyph in &letters.unknown {
+        eprintln!("Unknown glyph #{}:", glyph.index + 1);
+        for row in &glyph.rows {
+            eprintln!("    {row}");
         }
-        println!("");
+    }
+    if !letters.unknown.is_empty() {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,80 @@
+// Reads the CRT's block letters: glyphs are 4 pixels wide and 6 tall, with one dark column
+// between consecutive glyphs.
+#![allow(dead_code)]
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+const SPACING: usize = 1;
+
+// The letters that show up in puzzle inputs, the rest of the alphabet doesn't fit in 4x6
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+    (' ', ["....", "....", "....", "....", "....", "...."]),
+];
+
+pub struct Glyph {
+    // Index of the glyph, counting from the left
+    pub index: usize,
+    // Rows of the glyph as drawn, '#' lit and '.' dark
+    pub rows: Vec<String>,
+}
+
+pub struct Recognition {
+    // Recognized text, with '?' in place of unknown glyphs
+    pub text: String,
+    pub unknown: Vec<Glyph>,
+}
+
+fn lookup(rows: &[String]) -> Option<char> {
+    FONT.iter().find(|(_, glyph)| glyph.iter().zip(rows).all(|(a, b)| a == b)).map(|(ch, _)| *ch)
+}
+
+// Any character other than '#' counts as a dark pixel. A glyph whose spacing column isn't dark
+// is reported as unknown, as it's probably part of something wider.
+pub fn recognize(grid: &[Vec<char>]) -> Result<Recognition, String> {
+    if grid.len() != GLYPH_HEIGHT {
+        return Err(format!("expected {GLYPH_HEIGHT} rows of pixels, got {}.", grid.len()));
+    }
+    let width = grid[0].len();
+    if grid.iter().any(|row| row.len() != width) {
+        return Err(String::from("rows of pixels have different lengths."));
+    }
+    let pixel = |row: usize, col: usize| if grid[row][col] == '#' { '#' } else { '.' };
+
+    let mut res = Recognition { text: String::new(), unknown: Vec::new() };
+    let step = GLYPH_WIDTH + SPACING;
+    for (index, start) in (0..width).step_by(step).enumerate() {
+        if start + GLYPH_WIDTH > width {
+            return Err(format!("{} columns of pixels don't split into glyphs.", width));
+        }
+        let rows: Vec<String> = (0..GLYPH_HEIGHT)
+            .map(|r| (start..start + GLYPH_WIDTH).map(|c| pixel(r, c)).collect())
+            .collect();
+        let spaced = (start + GLYPH_WIDTH..(start + step).min(width))
+            .all(|c| (0..GLYPH_HEIGHT).all(|r| pixel(r, c) == '.'));
+        match lookup(&rows).filter(|_| spaced) {
+            Some(ch) => res.text.push(ch),
+            None => {
+                res.text.push('?');
+                res.unknown.push(Glyph { index: index, rows: rows });
+            }
+        }
+    }
+    Ok(res)
+}
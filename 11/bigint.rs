@@ -0,0 +1,178 @@
+// Arbitrary-precision unsigned integers, just enough for exact worry levels: +, checked -, *,
+// / and %, comparison and decimal output.
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::fmt;
+
+// Little-endian base 2^32 digits without leading zeros, so zero has no digits at all
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { digits: Vec::new() }
+    }
+
+    pub fn from_u64(x: u64) -> BigUint {
+        BigUint::normalized(vec![x as u32, (x >> 32) as u32])
+    }
+
+    fn normalized(mut digits: Vec<u32>) -> BigUint {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        BigUint { digits: digits }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits[..] {
+            [] => Some(0),
+            [a] => Some(a as u64),
+            [a, b] => Some(a as u64 | (b as u64) << 32),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> usize {
+        match self.digits.last() {
+            None => 0,
+            Some(top) => 32 * self.digits.len() - top.leading_zeros() as usize,
+        }
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.digits.get(i / 32).map_or(false, |d| d >> (i % 32) & 1 == 1)
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let mut digits = Vec::with_capacity(self.digits.len().max(other.digits.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..self.digits.len().max(other.digits.len()) {
+            let sum = carry + *self.digits.get(i).unwrap_or(&0) as u64 + *other.digits.get(i).unwrap_or(&0) as u64;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        digits.push(carry as u32);
+        BigUint::normalized(digits)
+    }
+
+    // None if the result would be negative
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut digits = Vec::with_capacity(self.digits.len());
+        let mut borrow = 0i64;
+        for i in 0..self.digits.len() {
+            let mut diff = self.digits[i] as i64 - *other.digits.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = if diff < 0 { 1 } else { 0 };
+            if diff < 0 {
+                diff += 1 << 32;
+            }
+            digits.push(diff as u32);
+        }
+        Some(BigUint::normalized(digits))
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.digits.iter().enumerate() {
+                let cur = digits[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                digits[i + j] = cur as u32;
+                carry = cur >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        BigUint::normalized(digits)
+    }
+
+    // Quotient and remainder by a small divisor, which must not be zero
+    fn divrem_small(&self, d: u32) -> (BigUint, u32) {
+        let mut digits = vec![0u32; self.digits.len()];
+        let mut rem = 0u64;
+        for i in (0..self.digits.len()).rev() {
+            let cur = rem << 32 | self.digits[i] as u64;
+            digits[i] = (cur / d as u64) as u32;
+            rem = cur % d as u64;
+        }
+        (BigUint::normalized(digits), rem as u32)
+    }
+
+    // Quotient and remainder, None when dividing by zero. Divisors of more than one digit go
+    // through bit by bit long division, slow but plenty for the sizes at hand.
+    pub fn divrem(&self, d: &BigUint) -> Option<(BigUint, BigUint)> {
+        match d.digits[..] {
+            [] => None,
+            [small] => {
+                let (q, r) = self.divrem_small(small);
+                Some((q, BigUint::from_u64(r as u64)))
+            }
+            _ => {
+                let mut q = vec![0u32; self.digits.len()];
+                let mut r = BigUint::zero();
+                for i in (0..self.bits()).rev() {
+                    r = r.add(&r);
+                    if self.bit(i) {
+                        r = r.add(&BigUint::from_u64(1));
+                    }
+                    if r >= *d {
+                        r = r.checked_sub(d).unwrap();
+                        q[i / 32] |= 1 << (i % 32);
+                    }
+                }
+                Some((BigUint::normalized(q), r))
+            }
+        }
+    }
+
+    pub fn rem_u64(&self, m: u64) -> u64 {
+        let mut rem = 0u128;
+        for d in self.digits.iter().rev() {
+            rem = (rem << 32 | *d as u128) % m as u128;
+        }
+        rem as u64
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.digits.len().cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Chunks of 9 decimal digits, least significant first
+        let mut chunks = Vec::new();
+        let mut x = self.clone();
+        while !x.is_zero() {
+            let (q, r) = x.divrem_small(1_000_000_000);
+            chunks.push(r);
+            x = q;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
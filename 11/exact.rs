@@ -0,0 +1,81 @@
+// Exact worry levels, growing without bounds, played alongside the bounded simulation to
+// check it round by round. Only practical for short runs: squaring doubles the digit count.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use crate::bigint::BigUint;
+use crate::expr::{BinOp, Value};
+use crate::monkey::{Monkey, Relief};
+
+impl Value for BigUint {
+    fn literal(x: u64) -> BigUint {
+        BigUint::from_u64(x)
+    }
+
+    fn apply(op: BinOp, a: &BigUint, b: &BigUint) -> Result<BigUint, String> {
+        match op {
+            BinOp::Add => Ok(a.add(b)),
+            BinOp::Sub => a.checked_sub(b).ok_or(String::from("worry level below zero.")),
+            BinOp::Mul => Ok(a.mul(b)),
+            BinOp::Div => a.divrem(b).map(|(q, _)| q).ok_or(String::from("division by zero.")),
+        }
+    }
+}
+
+pub struct Exact {
+    // Items held by every monkey as (id, worry level)
+    pub items: Vec<VecDeque<(usize, BigUint)>>,
+}
+
+impl Exact {
+    // Starts from the items the monkeys currently hold
+    pub fn new(monkeys: &[Monkey]) -> Exact {
+        Exact {
+            items: monkeys.iter()
+                .map(|m| m.items.iter().map(|h| (h.id, BigUint::from_u64(h.worry))).collect())
+                .collect(),
+        }
+    }
+
+    // Plays a round with the monkeys' rules; a modulo relief is only there to bound worry levels,
+    // so it's skipped here
+    pub fn play_round(&mut self, monkeys: &[Monkey], relief: Relief) -> Result<(), String> {
+        for (i, m) in monkeys.iter().enumerate() {
+            while let Some((id, worry)) = self.items[i].pop_front() {
+                let mut worry = m.operation.eval_as(&worry).map_err(|e| format!("Monkey {i}: {e}"))?;
+                if let Relief::Divide(d) = relief {
+                    worry = worry.divrem(&BigUint::from_u64(d)).ok_or(String::from("division by zero."))?.0;
+                }
+                let to = if worry.rem_u64(m.test_int) == 0 { m.if_yes } else { m.if_no };
+                self.items[to].push_back((id, worry));
+            }
+        }
+        Ok(())
+    }
+
+    // Checks that the bounded simulation holds the same items in the same order, with worry
+    // levels equal to the exact ones, modulo m for a modulo relief
+    pub fn check(&self, monkeys: &[Monkey], relief: Relief) -> Result<(), String> {
+        for (i, (m, exact)) in monkeys.iter().zip(&self.items).enumerate() {
+            if m.items.len() != exact.len() {
+                return Err(format!("monkey {i} holds {} items instead of {}.", m.items.len(), exact.len()));
+            }
+            for (held, (id, worry)) in m.items.iter().zip(exact) {
+                let expected = match relief {
+                    Relief::Divide(_) => worry.to_u64(),
+                    Relief::Modulo(modulus) => Some(worry.rem_u64(modulus)),
+                };
+                if held.id != *id || expected != Some(held.worry) {
+                    return Err(format!("monkey {i} holds item {} with worry level {} instead of item {id} with {worry}.",
+                        held.id, held.worry));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn largest(&self) -> Option<&BigUint> {
+        self.items.iter().flatten().map(|(_, worry)| worry).max()
+    }
+}
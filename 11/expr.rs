@@ -0,0 +1,187 @@
+// The right-hand side of a monkey's `new = ...` line: `old`, integer literals, + - * / and
+// parentheses, with the usual precedence and left associativity.
+#![allow(dead_code)]
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expr {
+    Old,
+    Literal(u64),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Old,
+    Number(u64),
+    Op(BinOp),
+    Open,
+    Close,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' => { chars.next(); }
+            '+' | '-' | '*' | '/' => {
+                chars.next();
+                tokens.push(Token::Op(match ch {
+                    '+' => BinOp::Add,
+                    '-' => BinOp::Sub,
+                    '*' => BinOp::Mul,
+                    _ => BinOp::Div,
+                }));
+            }
+            '(' => { chars.next(); tokens.push(Token::Open); }
+            ')' => { chars.next(); tokens.push(Token::Close); }
+            _ if ch.is_ascii_alphanumeric() => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric()) {
+                    word.push(c);
+                    chars.next();
+                }
+                if word == "old" {
+                    tokens.push(Token::Old);
+                } else {
+                    let x = word.parse().map_err(|_| format!("unexpected `{word}`."))?;
+                    tokens.push(Token::Number(x));
+                }
+            }
+            _ => return Err(format!("unexpected `{ch}`.")),
+        }
+    }
+    Ok(tokens)
+}
+
+// Recursive descent, one function per precedence level
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.pos += 1;
+        self.tokens.get(self.pos - 1).cloned()
+    }
+
+    // Left-associative chain of `operand (op operand)*` with ops taken from `ops`
+    fn chain(&mut self, ops: &[BinOp], operand: fn(&mut Parser) -> Result<Expr, String>) -> Result<Expr, String> {
+        let mut lhs = operand(self)?;
+        while let Some(Token::Op(op)) = self.peek().cloned() {
+            if !ops.contains(&op) {
+                break;
+            }
+            self.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(operand(self)?));
+        }
+        Ok(lhs)
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        self.chain(&[BinOp::Add, BinOp::Sub], Parser::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        self.chain(&[BinOp::Mul, BinOp::Div], Parser::atom)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Old) => Ok(Expr::Old),
+            Some(Token::Number(x)) => Ok(Expr::Literal(x)),
+            Some(Token::Open) => {
+                let inner = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err(String::from("missing `)`.")),
+                }
+            }
+            Some(_) => Err(String::from("expected `old`, a number or `(`.")),
+            None => Err(String::from("unexpected end of expression.")),
+        }
+    }
+}
+
+// Numbers expressions can be evaluated with
+pub trait Value: Clone {
+    fn literal(x: u64) -> Self;
+    fn apply(op: BinOp, a: &Self, b: &Self) -> Result<Self, String>;
+}
+
+impl Value for u64 {
+    fn literal(x: u64) -> u64 {
+        x
+    }
+
+    fn apply(op: BinOp, a: &u64, b: &u64) -> Result<u64, String> {
+        match op {
+            BinOp::Add => a.checked_add(*b).ok_or(String::from("worry level overflow.")),
+            BinOp::Sub => a.checked_sub(*b).ok_or(String::from("worry level below zero.")),
+            BinOp::Mul => a.checked_mul(*b).ok_or(String::from("worry level overflow.")),
+            BinOp::Div => a.checked_div(*b).ok_or(String::from("division by zero.")),
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(s: &str) -> Result<Expr, String> {
+        let mut parser = Parser { tokens: tokenize(s)?, pos: 0 };
+        let expr = parser.sum()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(String::from("unexpected tokens after the expression."));
+        }
+        Ok(expr)
+    }
+
+    pub fn eval(&self, old: u64) -> Result<u64, String> {
+        self.eval_as(&old)
+    }
+
+    pub fn eval_as<T: Value>(&self, old: &T) -> Result<T, String> {
+        match self {
+            Expr::Old => Ok(old.clone()),
+            Expr::Literal(x) => Ok(T::literal(*x)),
+            Expr::Binary(op, lhs, rhs) => T::apply(*op, &lhs.eval_as(old)?, &rhs.eval_as(old)?),
+        }
+    }
+
+    // Value modulo `m`, which only depends on `old` modulo `m` as long as there is no division
+    pub fn eval_mod(&self, old: u64, m: u64) -> Result<u64, String> {
+        let m = m as u128;
+        let res = match self {
+            Expr::Old => old as u128 % m,
+            Expr::Literal(x) => *x as u128 % m,
+            Expr::Binary(op, lhs, rhs) => {
+                let (a, b) = (lhs.eval_mod(old, m as u64)? as u128, rhs.eval_mod(old, m as u64)? as u128);
+                match op {
+                    BinOp::Add => (a + b) % m,
+                    BinOp::Sub => (a + m - b) % m,
+                    BinOp::Mul => a * b % m,
+                    BinOp::Div => return Err(String::from("division can't be done modulo the test divisors.")),
+                }
+            }
+        };
+        Ok(res as u64)
+    }
+
+    pub fn has_division(&self) -> bool {
+        match self {
+            Expr::Binary(op, lhs, rhs) => *op == BinOp::Div || lhs.has_division() || rhs.has_division(),
+            _ => false,
+        }
+    }
+}
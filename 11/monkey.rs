@@ -0,0 +1,164 @@
+// Monkeys passing items around, shared by both parts.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use crate::expr::Expr;
+
+pub type Item = u64;
+
+// An item being held, `id` telling items apart in order of appearance in the input
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Held {
+    pub id: usize,
+    pub worry: Item,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Inspection {
+    pub item: usize,
+    // Worry level before the inspection and after the relief
+    pub before: Item,
+    pub after: Item,
+    pub to: usize,
+}
+
+// How worry levels are kept manageable after every inspection
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Relief {
+    Divide(u64),
+    // Doesn't change the outcome of any test as long as every test divides the modulus
+    Modulo(u64),
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    pub items: VecDeque<Held>,
+    pub operation: Expr,
+    pub test_int: u64,
+    pub if_yes: usize,
+    pub if_no: usize,
+}
+
+impl Monkey {
+    pub fn new() -> Monkey {
+        Monkey {
+            items: VecDeque::new(),
+            operation: Expr::Old,
+            test_int: 1,
+            if_yes: 0,
+            if_no: 0,
+        }
+    }
+
+    // Takes the first item, the caller passes it on to `to`
+    pub fn inspect_item(&mut self, relief: Relief) -> Result<Option<Inspection>, String> {
+        let held = match self.items.pop_front() {
+            Some(held) => held,
+            None => return Ok(None),
+        };
+        let after = match relief {
+            Relief::Divide(d) => self.operation.eval(held.worry)? / d,
+            Relief::Modulo(m) => self.operation.eval_mod(held.worry, m)?,
+        };
+        let to = if after % self.test_int == 0 { self.if_yes } else { self.if_no };
+        Ok(Some(Inspection { item: held.id, before: held.worry, after: after, to: to }))
+    }
+}
+
+fn get_int<T: std::str::FromStr>(line: &str) -> Option<T> {
+    line.split(" ").last().unwrap().parse().ok()
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Monkey>, String> {
+    let mut monkeys: Vec<Monkey> = Vec::new();
+    let mut monkey = Monkey::new();
+    let mut init = false;
+    let mut next_id = 0;
+
+    for (i, line) in contents.lines().enumerate() {
+        let err = |msg: &str| format!("Line {}: {msg}", i + 1);
+        if line.starts_with("Monkey") {
+            if init {
+                monkeys.push(monkey.clone());
+                monkey = Monkey::new();
+            }
+            init = true;
+        } else if line.contains("Starting items") {
+            let (_, items) = line.split_once(": ").ok_or(err("expected `Starting items: ...`."))?;
+            for x in items.split(", ") {
+                let worry = x.trim().parse().map_err(|_| err("invalid item."))?;
+                monkey.items.push_back(Held { id: next_id, worry: worry });
+                next_id += 1;
+            }
+        } else if line.contains("Operation") {
+            let (_, op) = line.split_once(" = ").ok_or(err("expected `Operation: new = ...`."))?;
+            monkey.operation = Expr::parse(op).map_err(|e| err(&e))?;
+        } else if line.contains("Test") {
+            monkey.test_int = get_int(&line).filter(|x| *x > 0).ok_or(err("invalid divisor."))?;
+        } else if line.contains("If true") {
+            monkey.if_yes = get_int(&line).ok_or(err("invalid monkey."))?;
+        } else if line.contains("If false") {
+            monkey.if_no = get_int(&line).ok_or(err("invalid monkey."))?;
+        }
+    }
+    if init {
+        monkeys.push(monkey);
+    }
+
+    for (i, m) in monkeys.iter().enumerate() {
+        if m.if_yes >= monkeys.len() || m.if_no >= monkeys.len() || m.if_yes == i || m.if_no == i {
+            return Err(format!("Monkey {i} throws to an invalid monkey."));
+        }
+    }
+    Ok(monkeys)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// Smallest modulus keeping worry levels bounded without changing the outcome of any test: the
+// lcm of the divisors, which is their product only when they share no factors
+pub fn modulus(monkeys: &[Monkey]) -> Result<u64, String> {
+    let mut lcm: u64 = 1;
+    for m in monkeys {
+        lcm = (lcm / gcd(lcm, m.test_int)).checked_mul(m.test_int)
+            .ok_or(String::from("The lcm of the test divisors doesn't fit in 64 bits."))?;
+    }
+    Ok(lcm)
+}
+
+// Plays one round, calling `on_throw(monkey, inspection)` for every item thrown. Returns how many
+// items every monkey inspected during the round.
+pub fn play_round(monkeys: &mut Vec<Monkey>, relief: Relief, on_throw: &mut dyn FnMut(usize, &Inspection))
+        -> Result<Vec<u64>, String> {
+    let mut inspections = vec![0; monkeys.len()];
+    for i in 0..monkeys.len() {
+        while let Some(insp) = monkeys[i].inspect_item(relief).map_err(|e| format!("Monkey {i}: {e}"))? {
+            inspections[i] += 1;
+            on_throw(i, &insp);
+            monkeys[insp.to].items.push_back(Held { id: insp.item, worry: insp.after });
+        }
+    }
+    Ok(inspections)
+}
+
+// Plays the given number of rounds, returning how many items every monkey inspected
+pub fn simulate(monkeys: &mut Vec<Monkey>, rounds: usize, relief: Relief) -> Result<Vec<u64>, String> {
+    let mut inspections = vec![0; monkeys.len()];
+    for round in 0..rounds {
+        let counts = play_round(monkeys, relief, &mut |_, _| {}).map_err(|e| format!("Round {}: {e}", round + 1))?;
+        for (total, count) in inspections.iter_mut().zip(counts) {
+            *total += count;
+        }
+    }
+    Ok(inspections)
+}
+
+// Product of the two highest inspection counts
+pub fn monkey_business(inspections: &[u64]) -> u64 {
+    let mut sorted = inspections.to_vec();
+    sorted.sort_by(|x, y| y.cmp(x));
+    sorted.iter().take(2).product()
+}
@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::Path;
+use std::env;
+
+mod bigint;
+mod exact;
+mod expr;
+mod monkey;
+
+use exact::Exact;
+use monkey::Relief;
+
+// Usage: trace <file> [--part 1|2] [--rounds <n>] [--throws] [--per-round] [--exact]
+//              [--csv <out>] [--csv-per-round <out>] [--csv-flow <out>]
+// Replays the monkeys with part 1 or 2 rules (20 or 10000 rounds by default). Prints every throw
+// with --throws and inspection counts after every round with --per-round, then how many items
+// went from every monkey to every other one. --csv, --csv-per-round and --csv-flow write the
+// throws, the per-round inspection counts and the item flow to CSV files.
+// --exact also keeps exact worry levels and checks the simulation against them after every
+// round, exiting with 1 on the first difference. It runs 20 rounds by default, as the exact
+// values grow quickly.
+
+fn to_csv(table: &[Vec<String>]) -> String {
+    table.iter().map(|row| row.join(",") + "\n").collect()
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).expect("No filename provided."));
+    let contents = fs::read_to_string(&path).expect("Couldn't read file.");
+    let option = |name: &str| args.iter().position(|x| x == name).map(|i| args.get(i + 1).expect("Missing option value."));
+    let flag = |name: &str| args.contains(&String::from(name));
+    let part: u32 = option("--part").map_or(1, |x| x.parse().expect("Invalid part."));
+
+    let mut monkeys = monkey::parse(&contents).expect("Invalid input.");
+    let (relief, default_rounds) = match part {
+        1 => (Relief::Divide(3), 20),
+        2 => (Relief::Modulo(monkey::modulus(&monkeys).expect("Can't bound worry levels.")), 10000),
+        _ => panic!("Part must be 1 or 2."),
+    };
+    let mut exact = if flag("--exact") { Some(Exact::new(&monkeys)) } else { None };
+    let default_rounds = if exact.is_some() { 20 } else { default_rounds };
+    let rounds: usize = option("--rounds").map_or(default_rounds, |x| x.parse().expect("Invalid number of rounds."));
+
+    let n = monkeys.len();
+    let (show_throws, per_round) = (flag("--throws"), flag("--per-round"));
+    let mut csv = option("--csv").map(|_| String::from("round,monkey,item,before,after,to\n"));
+    // flow[i][j]: items thrown from monkey i to monkey j
+    let mut flow = vec![vec![0u64; n]; n];
+    let mut totals = vec![0u64; n];
+
+    // Table of per-round inspection counts, header first
+    let mut rounds_table: Vec<Vec<String>> = vec![
+        std::iter::once(String::from("round"))
+            .chain((0..n).map(|i| format!("m{i}")))
+            .chain(exact.as_ref().map(|_| String::from("digits")))
+            .collect()
+    ];
+    let csv_per_round = option("--csv-per-round");
+    if per_round {
+        println!("{}", rounds_table[0].join("\t"));
+    }
+    for round in 1..=rounds {
+        let counts = monkey::play_round(&mut monkeys, relief, &mut |i, insp| {
+            flow[i][insp.to] += 1;
+            if show_throws {
+                println!("round {round} monkey {i}: item {} worry {} -> {} thrown to monkey {}",
+                    insp.item, insp.before, insp.after, insp.to);
+            }
+            if let Some(out) = csv.as_mut() {
+                out.push_str(&format!("{round},{i},{},{},{},{}\n", insp.item, insp.before, insp.after, insp.to));
+            }
+        }).unwrap_or_else(|e| panic!("Round {}: {}", round, e));
+        for (total, count) in totals.iter_mut().zip(&counts) {
+            *total += count;
+        }
+        if let Some(exact) = exact.as_mut() {
+            let checked = exact.play_round(&monkeys, relief).and_then(|_| exact.check(&monkeys, relief));
+            if let Err(e) = checked {
+                eprintln!("Round {round}: exact check failed: {e}");
+                std::process::exit(1);
+            }
+        }
+        if per_round || csv_per_round.is_some() {
+            let mut cells: Vec<String> = std::iter::once(round.to_string())
+                .chain(counts.iter().map(|x| x.to_string()))
+                .collect();
+            if let Some(exact) = exact.as_ref() {
+                cells.push(exact.largest().map_or(0, |x| x.to_string().len()).to_string());
+            }
+            if per_round {
+                println!("{}", cells.join("\t"));
+            }
+            rounds_table.push(cells);
+        }
+    }
+
+    // Item flow, one row per thrower: items thrown to every monkey, then the total
+    let mut flow_table: Vec<Vec<String>> = vec![
+        std::iter::once(String::from("from"))
+            .chain((0..n).map(|j| format!("m{j}")))
+            .chain(std::iter::once(String::from("total")))
+            .collect()
+    ];
+    for (i, row) in flow.iter().enumerate() {
+        flow_table.push(std::iter::once(format!("m{i}"))
+            .chain(row.iter().map(|x| x.to_string()))
+            .chain(std::iter::once(totals[i].to_string()))
+            .collect());
+    }
+    println!("item flow (rows: from, columns: to):");
+    for row in &flow_table {
+        println!("{}", row.join("\t"));
+    }
+    println!("monkey business: {}", monkey::monkey_business(&totals));
+    if let Some(exact) = exact {
+        let largest = exact.largest().map_or(String::from("0"), |x| x.to_string());
+        println!("exact check passed for {rounds} rounds, largest worry level has {} digits", largest.len());
+    }
+
+    if let (Some(out), Some(csv)) = (option("--csv"), csv) {
+        fs::write(out, csv).expect("Couldn't write CSV.");
+    }
+    if let Some(out) = csv_per_round {
+        fs::write(out, to_csv(&rounds_table)).expect("Couldn't write CSV.");
+    }
+    if let Some(out) = option("--csv-flow") {
+        fs::write(out, to_csv(&flow_table)).expect("Couldn't write CSV.");
+    }
+}
@@ -0,0 +1,83 @@
+// Heightmap with distances to the goal for every cell, from a single BFS going backwards from
+// the goal.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+pub type Point = (usize, usize);
+pub type Grid = Vec<Vec<u32>>;
+
+pub struct Heightmap {
+    pub grid: Grid,
+    pub start: Point,
+    pub goal: Point,
+}
+
+fn char_to_height(ch: char) -> u32 {
+    match ch {
+        'S' => char_to_height('a'),
+        'E' => char_to_height('z'),
+        _ => ch as u32
+    }
+}
+
+impl Heightmap {
+    pub fn parse(contents: &str) -> Result<Heightmap, String> {
+        let mut grid: Grid = Vec::new();
+        let (mut start, mut goal) = (None, None);
+        for (row, line) in contents.lines().filter(|x| !x.is_empty()).enumerate() {
+            grid.push(Vec::new());
+            for (col, ch) in line.chars().enumerate() {
+                match ch {
+                    'S' => start = Some((row, col)),
+                    'E' => goal = Some((row, col)),
+                    'a'..='z' => {}
+                    _ => return Err(format!("Line {}: unexpected `{ch}`.", row + 1)),
+                }
+                grid[row].push(char_to_height(ch));
+            }
+            if grid[row].len() != grid[0].len() {
+                return Err(format!("Line {}: rows have different lengths.", row + 1));
+            }
+        }
+        Ok(Heightmap {
+            grid: grid,
+            start: start.ok_or(String::from("No start `S`."))?,
+            goal: goal.ok_or(String::from("No goal `E`."))?,
+        })
+    }
+
+    pub fn height(&self, p: Point) -> u32 {
+        self.grid[p.0][p.1]
+    }
+
+    fn neighbours(&self, p: Point) -> Vec<Point> {
+        let (rows, cols) = (self.grid.len(), self.grid[0].len());
+        let mut res = Vec::with_capacity(4);
+        if p.0 > 0 { res.push((p.0 - 1, p.1)); }
+        if p.0 + 1 < rows { res.push((p.0 + 1, p.1)); }
+        if p.1 > 0 { res.push((p.0, p.1 - 1)); }
+        if p.1 + 1 < cols { res.push((p.0, p.1 + 1)); }
+        res
+    }
+
+    // Fewest steps from every cell to the goal, None if it can't be reached. A step from p to q
+    // is allowed if q is at most one higher than p, so going backwards from the goal we move
+    // from q to p whenever p is at least height(q) - 1.
+    pub fn distances_to_goal(&self) -> Vec<Vec<Option<u32>>> {
+        let mut dist = vec![vec![None; self.grid[0].len()]; self.grid.len()];
+        let mut queue: VecDeque<Point> = VecDeque::new();
+        dist[self.goal.0][self.goal.1] = Some(0);
+        queue.push_back(self.goal);
+        while let Some(q) = queue.pop_front() {
+            let d = dist[q.0][q.1].unwrap();
+            for p in self.neighbours(q) {
+                if dist[p.0][p.1].is_none() && self.height(p) + 1 >= self.height(q) {
+                    dist[p.0][p.1] = Some(d + 1);
+                    queue.push_back(p);
+                }
+            }
+        }
+        dist
+    }
+}
@@ -3,7 +3,11 @@ use std::path::Path;
 use std::env;
 use std::io::{BufReader, BufRead};
 use std::collections::HashSet;
-use std::cmp::{max, min};
+
+#[path = "../common/interval.rs"]
+mod interval;
+
+use interval::{Interval, IntervalSet};
 
 type Point = (i32, i32);
 
@@ -19,13 +23,13 @@ fn parse_number(a: &str) -> i32 {
     a[l+1..].parse().unwrap()
 }
 
-// Returns (min_x, max_x) for row y, None if not blocking anything
-fn inaccessible(s: Point, b: Point, y: i32) -> Option<(i32, i32)> {
+// Returns [min_x, max_x] for row y, None if not blocking anything
+fn inaccessible(s: Point, b: Point, y: i32) -> Option<Interval> {
     let dist = (s.0 - b.0).abs() + (s.1 - b.1).abs();
     let y_dist = (s.1 - y).abs();
     let rem = dist - y_dist;
     if rem >= 0 {
-        Some((s.0 - rem, s.0 + rem))
+        Some(Interval((s.0 - rem) as i64, (s.0 + rem) as i64))
     } else {
         None
     }
@@ -37,8 +41,7 @@ fn main() {
     let file = File::open(&path).expect("Couldn't open file.");
     let lines = BufReader::new(file).lines();
 
-    let mut min_x = i32::MAX;
-    let mut max_x = i32::MIN;
+    let mut cover = IntervalSet::new();
     let y: i32 = 2000000;
     let mut balises_problematiques: HashSet<i32> = HashSet::new();
     for line in lines {
@@ -51,13 +54,12 @@ fn main() {
             if by == y {
                 balises_problematiques.insert(bx);
             }
-            if let Some((x1, x2)) = inaccessible((sx, sy), (bx, by), y) {
-                min_x = min(min_x, x1);
-                max_x = max(max_x, x2);
+            if let Some(int) = inaccessible((sx, sy), (bx, by), y) {
+                cover.insert(int);
             }
         }
     }
 
-    let b_count = balises_problematiques.iter().filter(|&x| *x >= min_x && *x <= max_x).count() as i32;
-    println!("{}", max_x - min_x + 1 - b_count);
+    let b_count = balises_problematiques.iter().filter(|&x| cover.contains_point(*x as i64)).count() as i64;
+    println!("{}", cover.covered_len() - b_count);
 }
@@ -0,0 +1,210 @@
+// Rules for cyclic rock-paper-scissors style games with any odd number of moves.
+// Moves are numbered 0..n and move b beats move a iff (b - a) mod n is odd, so every move
+// beats exactly (n - 1) / 2 others. For n = 3 that's Rock, Paper, Scissors and for n = 5
+// it's Rock, Paper, Scissors, Spock, Lizard.
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{BufReader, BufRead};
+use std::path::Path;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Outcome { Loss, Draw, Win }
+
+impl Outcome {
+    // X, Y, Z in the second interpretation of the strategy guide
+    pub fn from(c: char) -> Option<Outcome> {
+        match c {
+            'X' => Some(Outcome::Loss),
+            'Y' => Some(Outcome::Draw),
+            'Z' => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Interpretation {
+    // Part 1: the second column is the move to play
+    Move,
+    // Part 2: the second column is the outcome to aim for
+    Outcome,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rules {
+    pub names: Vec<String>,
+    pub scores: Vec<u32>,
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+}
+
+impl Rules {
+    pub fn classic() -> Rules {
+        Rules::named(&["Rock", "Paper", "Scissors"])
+    }
+
+    pub fn lizard_spock() -> Rules {
+        Rules::named(&["Rock", "Paper", "Scissors", "Spock", "Lizard"])
+    }
+
+    // Anonymous moves scoring 1..=n
+    pub fn with_moves(n: usize) -> Result<Rules, String> {
+        if n % 2 == 0 || n < 3 || n > 13 {
+            return Err(format!("Need an odd number of moves from 3 to 13, got {n}."));
+        }
+        let names: Vec<String> = (0..n).map(|i| format!("Move{}", i + 1)).collect();
+        Ok(Rules::named(&names.iter().map(|x| x.as_str()).collect::<Vec<&str>>()))
+    }
+
+    fn named(names: &[&str]) -> Rules {
+        Rules {
+            names: names.iter().map(|x| String::from(*x)).collect(),
+            scores: (1..=names.len() as u32).collect(),
+            win: 6,
+            draw: 3,
+            loss: 0,
+        }
+    }
+
+    // Understands `--game classic|rpsls|<n>`, `--scores 1,2,3` and `--payout <win>,<draw>,<loss>`
+    pub fn from_args(args: &[String]) -> Result<Rules, String> {
+        let mut options: Vec<(&str, &str)> = Vec::new();
+        for pair in args.chunks(2) {
+            match pair {
+                [name, value] if ["--game", "--scores", "--payout"].contains(&name.as_str()) => {
+                    options.push((name, value))
+                }
+                [name, _] => return Err(format!("Unknown option: {name}")),
+                [name] => return Err(format!("Missing value for {name}.")),
+                _ => unreachable!(),
+            }
+        }
+        let option = |name: &str| options.iter().find(|(k, _)| *k == name).map(|(_, v)| *v);
+
+        let mut rules = match option("--game") {
+            None | Some("classic") => Rules::classic(),
+            Some("rpsls") => Rules::lizard_spock(),
+            Some(n) => Rules::with_moves(n.parse().map_err(|_| format!("Unknown game: {n}"))?)?,
+        };
+        if let Some(scores) = option("--scores") {
+            let scores = parse_list(scores)?;
+            if scores.len() != rules.len() {
+                return Err(format!("Expected {} move scores, got {}.", rules.len(), scores.len()));
+            }
+            rules.scores = scores;
+        }
+        if let Some(payout) = option("--payout") {
+            match parse_list(payout)?[..] {
+                [win, draw, loss] => {
+                    rules.win = win;
+                    rules.draw = draw;
+                    rules.loss = loss;
+                }
+                _ => return Err(String::from("Payout must be <win>,<draw>,<loss>.")),
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn outcome(&self, opponent: usize, player: usize) -> Outcome {
+        let diff = (player + self.len() - opponent) % self.len();
+        if diff == 0 {
+            Outcome::Draw
+        } else if diff % 2 == 1 {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    pub fn payout(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Loss => self.loss,
+        }
+    }
+
+    pub fn round_score(&self, opponent: usize, player: usize) -> u32 {
+        self.scores[player] + self.payout(self.outcome(opponent, player))
+    }
+
+    // The move that gets the given outcome against the opponent. With more than three
+    // moves there are several, in which case the best scoring one is picked.
+    pub fn move_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.len())
+            .filter(|p| self.outcome(opponent, *p) == outcome)
+            .max_by_key(|p| (self.scores[*p], usize::MAX - p))
+            .unwrap()
+    }
+
+    // The opponent plays 'A', 'B', ...; the player's column is the n letters ending at 'Z'
+    pub fn opponent_move(&self, c: char) -> Option<usize> {
+        let i = (c as usize).wrapping_sub('A' as usize);
+        if i < self.len() { Some(i) } else { None }
+    }
+
+    pub fn player_move(&self, c: char) -> Option<usize> {
+        let i = (c as usize).wrapping_sub('Z' as usize + 1 - self.len());
+        if i < self.len() { Some(i) } else { None }
+    }
+
+    pub fn player_letter(&self, player: usize) -> char {
+        (('Z' as u8 + 1 - self.len() as u8) + player as u8) as char
+    }
+}
+
+fn parse_list(s: &str) -> Result<Vec<u32>, String> {
+    s.split(",").map(|x| x.trim().parse().map_err(|_| format!("Invalid number: {x}"))).collect()
+}
+
+// One line of the strategy guide, still uninterpreted
+#[derive(Copy, Clone, Debug)]
+pub struct Round {
+    pub opponent: usize,
+    pub column: char,
+}
+
+impl Round {
+    pub fn player_move(&self, rules: &Rules, interpretation: Interpretation) -> Option<usize> {
+        match interpretation {
+            Interpretation::Move => rules.player_move(self.column),
+            Interpretation::Outcome => Outcome::from(self.column).map(|o| rules.move_for(self.opponent, o)),
+        }
+    }
+}
+
+pub fn load_guide(path: &Path, rules: &Rules) -> Result<Vec<Round>, String> {
+    let file = File::open(path).map_err(|e| format!("Couldn't open file: {e}"))?;
+    let mut guide = Vec::new();
+    for (i, line_res) in BufReader::new(file).lines().enumerate() {
+        let line = line_res.map_err(|e| format!("Couldn't read file: {e}"))?;
+        let line: Vec<char> = line.chars().collect();
+        if line.is_empty() {
+            continue;
+        }
+        if line.len() != 3 || line[1] != ' ' {
+            return Err(format!("Line {}: expected two letters separated by a space.", i + 1));
+        }
+        let opponent = rules.opponent_move(line[0])
+            .ok_or(format!("Line {}: unknown opponent move '{}'.", i + 1, line[0]))?;
+        guide.push(Round { opponent: opponent, column: line[2] });
+    }
+    Ok(guide)
+}
+
+pub fn total_score(rules: &Rules, guide: &[Round], interpretation: Interpretation) -> Result<u32, String> {
+    let mut sum: u32 = 0;
+    for (i, round) in guide.iter().enumerate() {
+        let player = round.player_move(rules, interpretation)
+            .ok_or(format!("Round {}: can't interpret '{}'.", i + 1, round.column))?;
+        sum += rules.round_score(round.opponent, player);
+    }
+    Ok(sum)
+}
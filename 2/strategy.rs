@@ -0,0 +1,185 @@
+use std::path::Path;
+use std::env;
+
+mod game;
+
+use game::{Interpretation, Round, Rules};
+
+// Usage:
+//   strategy optimize <guide> [--top <k>] [rules options]
+//   strategy tournament <guide> <guide>... [--as move|outcome] [rules options]
+// where the rules options are the same as for main1/main2. Optimize lists the k best mappings
+// of the player's letters (10 by default) and handles up to 11 moves.
+
+// Total if we always played the best move against the opponent's column
+fn best_total(rules: &Rules, guide: &[Round]) -> u32 {
+    guide.iter()
+        .map(|r| (0..rules.len()).map(|p| rules.round_score(r.opponent, p)).max().unwrap())
+        .sum()
+}
+
+// Trying every mapping of more moves than this takes too long
+const MAX_OPTIMIZE_MOVES: usize = 11;
+
+// Calls `visit` with every ordering of 0..n, generated in place with Heap's algorithm
+fn for_each_permutation(n: usize, visit: &mut dyn FnMut(&[usize])) {
+    let mut perm: Vec<usize> = (0..n).collect();
+    // c[i]: how many swaps were done at level i so far
+    let mut c = vec![0; n];
+    visit(&perm);
+    let mut i = 1;
+    while i < n {
+        if c[i] < i {
+            if i % 2 == 0 {
+                perm.swap(0, i);
+            } else {
+                perm.swap(c[i], i);
+            }
+            visit(&perm);
+            c[i] += 1;
+            i = 1;
+        } else {
+            c[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+// scores[c][m]: points scored by the rounds with column c when c is read as move m
+fn column_scores(rules: &Rules, guide: &[Round]) -> Option<Vec<Vec<u32>>> {
+    let mut scores = vec![vec![0; rules.len()]; rules.len()];
+    for r in guide {
+        let column = rules.player_move(r.column)?;
+        for m in 0..rules.len() {
+            scores[column][m] += rules.round_score(r.opponent, m);
+        }
+    }
+    Some(scores)
+}
+
+// Prints the `top` best ways to map the player's letters to moves, ties in lexicographic order
+fn optimize(rules: &Rules, guide: &[Round], top: usize) -> Result<(), String> {
+    if rules.len() > MAX_OPTIMIZE_MOVES {
+        return Err(format!("Can't try every mapping of more than {MAX_OPTIMIZE_MOVES} moves."));
+    }
+    let scores = column_scores(rules, guide).ok_or("Invalid strategy guide.")?;
+    println!("best possible: {}", best_total(rules, guide));
+
+    // Sorted best first
+    let mut best: Vec<(u32, Vec<usize>)> = Vec::with_capacity(top + 1);
+    let key = |score: u32, mapping: &[usize]| (std::cmp::Reverse(score), mapping.to_vec());
+    for_each_permutation(rules.len(), &mut |m| {
+        let total: u32 = m.iter().enumerate().map(|(c, x)| scores[c][*x]).sum();
+        if best.len() == top && best.last().map_or(true, |w| key(w.0, &w.1) <= key(total, m)) {
+            return;
+        }
+        let pos = best.partition_point(|b| key(b.0, &b.1) < key(total, m));
+        best.insert(pos, (total, m.to_vec()));
+        best.truncate(top);
+    });
+    for (score, mapping) in best {
+        let desc: Vec<String> = mapping.iter().enumerate()
+            .map(|(i, m)| format!("{}={}", rules.player_letter(i), rules.names[*m]))
+            .collect();
+        println!("{}: {score}", desc.join(" "));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default)]
+struct Standing {
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    points: u32,
+}
+
+// Every guide plays every other one, so they must all have the same number of rounds. In a match
+// round i pits the i-th move of one guide against the i-th move of the other, and the match goes
+// to whoever scores more points.
+fn tournament(rules: &Rules, names: &[String], players: &[Vec<usize>]) -> Result<(), String> {
+    for (name, moves) in names.iter().zip(players) {
+        if moves.len() != players[0].len() {
+            return Err(format!("{name} has {} rounds but {} has {}.", moves.len(), names[0], players[0].len()));
+        }
+    }
+    let mut standings = vec![Standing::default(); players.len()];
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (mut a, mut b) = (0, 0);
+            for (x, y) in players[i].iter().zip(&players[j]) {
+                a += rules.round_score(*y, *x);
+                b += rules.round_score(*x, *y);
+            }
+            standings[i].points += a;
+            standings[j].points += b;
+            if a > b {
+                standings[i].wins += 1;
+                standings[j].losses += 1;
+            } else if a < b {
+                standings[i].losses += 1;
+                standings[j].wins += 1;
+            } else {
+                standings[i].draws += 1;
+                standings[j].draws += 1;
+            }
+        }
+    }
+
+    let mut ranking: Vec<usize> = (0..players.len()).collect();
+    ranking.sort_by_key(|i| (std::cmp::Reverse(3 * standings[*i].wins + standings[*i].draws),
+                             std::cmp::Reverse(standings[*i].points)));
+    println!("rank\twins\tdraws\tlosses\tpoints\tguide");
+    for (rank, i) in ranking.iter().enumerate() {
+        let s = &standings[*i];
+        println!("{}\t{}\t{}\t{}\t{}\t{}", rank + 1, s.wins, s.draws, s.losses, s.points, names[*i]);
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let mode = args.get(1).expect("No mode provided.");
+    let files: Vec<String> = args[2..].iter().take_while(|x| !x.starts_with("--")).cloned().collect();
+    let mut options: Vec<String> = args[2 + files.len()..].to_vec();
+
+    let mut interpretation = Interpretation::Move;
+    if let Some(i) = options.iter().position(|x| x == "--as") {
+        interpretation = match options.get(i + 1).map(|x| x.as_str()) {
+            Some("move") => Interpretation::Move,
+            Some("outcome") => Interpretation::Outcome,
+            _ => panic!("--as must be `move` or `outcome`."),
+        };
+        options.drain(i..i + 2);
+    }
+    let mut top = 10;
+    if let Some(i) = options.iter().position(|x| x == "--top") {
+        top = options.get(i + 1).and_then(|x| x.parse().ok()).expect("Invalid number of mappings.");
+        options.drain(i..i + 2);
+    }
+    let rules = Rules::from_args(&options).expect("Invalid game options.");
+    let guides: Vec<Vec<Round>> = files.iter()
+        .map(|f| game::load_guide(Path::new(f), &rules).expect("Couldn't read strategy guide."))
+        .collect();
+
+    match mode.as_str() {
+        "optimize" => {
+            for (name, guide) in files.iter().zip(&guides) {
+                if files.len() > 1 {
+                    println!("{name}:");
+                }
+                optimize(&rules, guide, top).expect("Couldn't optimize.");
+            }
+        }
+        "tournament" => {
+            if guides.len() < 2 {
+                panic!("A tournament needs at least two guides.");
+            }
+            let players: Vec<Vec<usize>> = guides.iter()
+                .map(|g| g.iter().map(|r| r.player_move(&rules, interpretation).expect("Invalid strategy guide.")).collect())
+                .collect();
+            tournament(&rules, &files, &players).expect("Invalid tournament.");
+        }
+        _ => panic!("Unknown mode, expected `optimize` or `tournament`."),
+    }
+}
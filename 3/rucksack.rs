@@ -0,0 +1,142 @@
+// Rucksacks as 52-bit item masks: bit i is set iff the item with priority i + 1 is present.
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{BufReader, BufRead};
+use std::path::Path;
+
+pub type Mask = u64;
+
+const ALL_ITEMS: Mask = (1 << 52) - 1;
+
+pub fn priority(ch: u8) -> Option<u8> {
+    match ch {
+        b'a'..=b'z' => Some(ch - b'a' + 1),
+        b'A'..=b'Z' => Some(ch - b'A' + 27),
+        _ => None,
+    }
+}
+
+pub fn item(priority: u8) -> char {
+    if priority <= 26 {
+        (b'a' + priority - 1) as char
+    } else {
+        (b'A' + priority - 27) as char
+    }
+}
+
+pub fn mask(items: &[u8]) -> Result<Mask, String> {
+    let mut res: Mask = 0;
+    for ch in items {
+        let p = priority(*ch).ok_or(format!("Invalid item '{}'.", *ch as char))?;
+        res |= 1 << (p - 1);
+    }
+    Ok(res)
+}
+
+// Priorities of the items in the mask, ascending
+pub fn priorities(mask: Mask) -> Vec<u8> {
+    (0..52).filter(|i| mask & (1 << i) != 0).map(|i| i + 1).collect()
+}
+
+// A set of masks that should have exactly one item in common, with the 1-based
+// line range it came from
+pub struct Group {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub masks: Vec<Mask>,
+}
+
+impl Group {
+    pub fn shared(&self) -> Mask {
+        self.masks.iter().fold(ALL_ITEMS, |acc, m| acc & m)
+    }
+
+    pub fn describe(&self) -> String {
+        if self.first_line == self.last_line {
+            format!("line {}", self.first_line)
+        } else {
+            format!("lines {}-{}", self.first_line, self.last_line)
+        }
+    }
+}
+
+// Non-blank lines with their 1-based line numbers in the file
+pub fn read_lines(path: &Path) -> Result<Vec<(usize, String)>, String> {
+    let file = File::open(path).map_err(|e| format!("Couldn't open file: {e}"))?;
+    let lines: Result<Vec<String>, _> = BufReader::new(file).lines().collect();
+    let lines = lines.map_err(|e| format!("Couldn't read lines: {e}"))?;
+    Ok(lines.into_iter().enumerate().filter(|(_, x)| !x.is_empty()).map(|(i, x)| (i + 1, x)).collect())
+}
+
+// Each line is one group made of `count` equally sized compartments
+pub fn compartment_groups(lines: &[(usize, String)], count: usize) -> Result<Vec<Group>, String> {
+    let mut groups = Vec::new();
+    for (number, line) in lines {
+        let line = line.as_bytes();
+        if line.len() % count != 0 {
+            return Err(format!("Line {number}: {} items can't be split into {} compartments.", line.len(), count));
+        }
+        let masks: Result<Vec<Mask>, String> = line.chunks(line.len() / count).map(mask).collect();
+        let masks = masks.map_err(|e| format!("Line {number}: {e}"))?;
+        groups.push(Group { first_line: *number, last_line: *number, masks: masks });
+    }
+    Ok(groups)
+}
+
+// Every `size` consecutive non-blank lines form a group
+pub fn line_groups(lines: &[(usize, String)], size: usize) -> Result<Vec<Group>, String> {
+    if lines.len() % size != 0 {
+        return Err(format!("{} lines can't be split into groups of {}.", lines.len(), size));
+    }
+    let mut groups = Vec::new();
+    for chunk in lines.chunks(size) {
+        let (first, last) = (chunk[0].0, chunk[size - 1].0);
+        let masks: Result<Vec<Mask>, String> = chunk.iter().map(|(_, x)| mask(x.as_bytes())).collect();
+        let masks = masks.map_err(|e| format!("Lines {first}-{last}: {e}"))?;
+        groups.push(Group { first_line: first, last_line: last, masks: masks });
+    }
+    Ok(groups)
+}
+
+// Prints the shared item of every group if `verbose`, reports groups that don't share
+// exactly one item on stderr, and returns (sum of priorities of the valid groups, error count)
+pub fn report(groups: &[Group], verbose: bool) -> (u32, usize) {
+    let mut sum: u32 = 0;
+    let mut errors = 0;
+    for group in groups {
+        let shared = priorities(group.shared());
+        match shared.len() {
+            1 => {
+                if verbose {
+                    println!("{}: {} (priority {})", group.describe(), item(shared[0]), shared[0]);
+                }
+                sum += shared[0] as u32;
+            }
+            0 => {
+                eprintln!("{}: no shared item", group.describe());
+                errors += 1;
+            }
+            _ => {
+                let desc: Vec<String> = shared.iter().map(|p| format!("{} ({p})", item(*p))).collect();
+                eprintln!("{}: multiple shared items {}", group.describe(), desc.join(", "));
+                errors += 1;
+            }
+        }
+    }
+    (sum, errors)
+}
+
+// Value of `--name N` among the arguments, or `default`
+pub fn numeric_option(args: &[String], name: &str, default: usize) -> usize {
+    match args.iter().position(|x| x == name) {
+        Some(i) => {
+            let n: usize = args.get(i + 1).and_then(|x| x.parse().ok()).expect("Invalid numeric option.");
+            if n == 0 {
+                panic!("{} must be positive.", name);
+            }
+            n
+        }
+        None => default,
+    }
+}
@@ -3,16 +3,13 @@ use std::fs::File;
 use std::path::Path;
 use std::env;
 
-#[derive(Copy, Clone)]
-struct Interval(u32, u32);
+#[path = "../common/interval.rs"]
+mod interval;
 
-// Checks if a is fully contained in b
-fn fully_contained(a: Interval, b: Interval) -> bool {
-	a.0 >= b.0 && a.1 <= b.1
-}
+use interval::Interval;
 
 fn any_fully_contained(a: Interval, b: Interval) -> bool {
-	fully_contained(a, b) || fully_contained(b, a)
+	a.contains(&b) || b.contains(&a)
 }
 
 fn parse_interval(s: &str) -> Interval {
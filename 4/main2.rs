@@ -3,12 +3,10 @@ use std::fs::File;
 use std::path::Path;
 use std::env;
 
-#[derive(Copy, Clone)]
-struct Interval(u32, u32);
+#[path = "../common/interval.rs"]
+mod interval;
 
-fn overlap(a: Interval, b: Interval) -> bool {
-	a.0 <= b.1 && a.1 >= b.0
-}
+use interval::Interval;
 
 fn parse_interval(s: &str) -> Interval {
 	let (s, e) = s.split_once("-").expect("Invalid input.");
@@ -26,7 +24,7 @@ fn main() {
 		if let Ok(line) = line_res {
 			if line.len() > 6 {
 				let (a, b) = line.split_once(",").expect("Invalid input");
-				sum += overlap(parse_interval(a), parse_interval(b)) as u32;
+				sum += parse_interval(a).overlaps(&parse_interval(b)) as u32;
 			}
 		}
 	}
@@ -0,0 +1,112 @@
+use std::io::{BufReader, BufRead};
+use std::fs::File;
+use std::path::Path;
+use std::env;
+
+#[path = "../common/interval.rs"]
+mod interval;
+
+use interval::{Interval, IntervalSet};
+
+// Analysis of all assignments in the file at once. Elves are numbered from 1 in the order
+// they appear, so the pair on line n is elves 2n - 1 and 2n.
+
+fn parse_interval(s: &str) -> Interval {
+	let (s, e) = s.split_once("-").expect("Invalid input.");
+	Interval(s.parse().unwrap(), e.parse().unwrap())
+}
+
+// Splits the sections into stretches with a constant number of elves assigned.
+// Returns (stretch, number of elves) for every stretch between the first and the last section.
+fn sweep(elves: &[Interval]) -> Vec<(Interval, usize)> {
+	let mut events: Vec<(i64, i64)> = Vec::new();
+	for e in elves {
+		events.push((e.0, 1));
+		events.push((e.1 + 1, -1));
+	}
+	events.sort();
+
+	let mut res = Vec::new();
+	let mut count: i64 = 0;
+	let mut i = 0;
+	while i < events.len() {
+		let pos = events[i].0;
+		while i < events.len() && events[i].0 == pos {
+			count += events[i].1;
+			i += 1;
+		}
+		if i < events.len() {
+			res.push((Interval(pos, events[i].0 - 1), count as usize));
+		}
+	}
+	res
+}
+
+// Groups of elves connected through overlapping assignments, with the span each group covers
+fn overlap_groups(elves: &[Interval]) -> Vec<(Interval, Vec<usize>)> {
+	let mut order: Vec<usize> = (0..elves.len()).collect();
+	order.sort_by_key(|i| elves[*i]);
+
+	let mut groups: Vec<(Interval, Vec<usize>)> = Vec::new();
+	for i in order {
+		match groups.last_mut() {
+			Some((span, members)) if elves[i].0 <= span.1 => {
+				span.1 = span.1.max(elves[i].1);
+				members.push(i);
+			}
+			_ => groups.push((elves[i], vec![i])),
+		}
+	}
+	for (_, members) in groups.iter_mut() {
+		members.sort();
+	}
+	groups
+}
+
+fn elf_list(elves: &[usize]) -> String {
+	elves.iter().map(|x| (x + 1).to_string()).collect::<Vec<String>>().join(", ")
+}
+
+fn main() {
+	let args: Vec<String> = env::args().collect();
+	let path = Path::new(args.get(1).expect("No filename provided."));
+	let file = File::open(&path).expect("Couldn't open file.");
+	let lines = BufReader::new(file).lines();
+
+	let mut elves: Vec<Interval> = Vec::new();
+	for line_res in lines {
+		if let Ok(line) = line_res {
+			if line.len() > 6 {
+				let (a, b) = line.split_once(",").expect("Invalid input");
+				elves.push(parse_interval(a));
+				elves.push(parse_interval(b));
+			}
+		}
+	}
+	if elves.is_empty() {
+		panic!("No assignments.");
+	}
+
+	let stretches = sweep(&elves);
+	let max_count = stretches.iter().map(|(_, c)| *c).max().unwrap();
+	let busiest: Vec<String> = stretches.iter().filter(|(_, c)| *c == max_count)
+		.map(|(s, _)| if s.len() == 1 { s.0.to_string() } else { format!("{}-{}", s.0, s.1) })
+		.collect();
+	println!("max elves on one section: {max_count} (sections {})", busiest.join(", "));
+
+	let covered = IntervalSet::from_intervals(stretches.iter().filter(|(_, c)| *c > 0).map(|(s, _)| *s));
+	let span = Interval(covered.first().unwrap().0, covered.last().unwrap().1);
+	let gaps: Vec<String> = covered.gaps(span).iter().map(|g| format!("{}-{}", g.0, g.1)).collect();
+	println!("uncovered sections: {}", if gaps.is_empty() { String::from("none") } else { gaps.join(", ") });
+
+	// An elf is redundant iff none of its sections is covered by it alone
+	let single = IntervalSet::from_intervals(stretches.iter().filter(|(_, c)| *c == 1).map(|(s, _)| *s));
+	let redundant: Vec<usize> = (0..elves.len()).filter(|i| !single.overlaps(&elves[*i])).collect();
+	println!("redundant elves: {}", if redundant.is_empty() { String::from("none") } else { elf_list(&redundant) });
+
+	let groups = overlap_groups(&elves);
+	println!("overlap groups: {}", groups.len());
+	for (span, members) in groups {
+		println!("  {}-{}: elves {}", span.0, span.1, elf_list(&members));
+	}
+}
@@ -0,0 +1,222 @@
+// Cranes and a simulation of the rearrangement procedure that can be stepped back and forth.
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::stacks::{self, Move, Stacks};
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    // Given the crates lifted off the source stack (bottom first), returns them in the
+    // order they end up on the destination stack (bottom first)
+    fn arrange(&self, block: Vec<char>) -> Vec<char>;
+}
+
+// Moves one crate at a time
+pub struct CrateMover9000;
+
+// Moves all crates at once
+pub struct CrateMover9001;
+
+// Moves up to max_lift crates at once, starting from the top
+pub struct LimitedLift {
+    pub max_lift: usize,
+}
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9000")
+    }
+
+    fn arrange(&self, mut block: Vec<char>) -> Vec<char> {
+        block.reverse();
+        block
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9001")
+    }
+
+    fn arrange(&self, block: Vec<char>) -> Vec<char> {
+        block
+    }
+}
+
+impl Crane for LimitedLift {
+    fn name(&self) -> String {
+        format!("crane lifting up to {} crates", self.max_lift)
+    }
+
+    fn arrange(&self, block: Vec<char>) -> Vec<char> {
+        block.rchunks(self.max_lift).flat_map(|x| x.iter().copied()).collect()
+    }
+}
+
+// `9000`, `9001` or `lift:<n>`
+pub fn crane_from_name(name: &str) -> Option<Box<dyn Crane>> {
+    match name {
+        "9000" => Some(Box::new(CrateMover9000)),
+        "9001" => Some(Box::new(CrateMover9001)),
+        _ => {
+            let n: usize = name.strip_prefix("lift:")?.parse().ok()?;
+            if n == 0 { None } else { Some(Box::new(LimitedLift { max_lift: n })) }
+        }
+    }
+}
+
+pub struct Simulation {
+    crane: Box<dyn Crane>,
+    pub stacks: Stacks,
+    moves: Vec<Move>,
+    // Crates taken off the source stack by every applied move, bottom first.
+    // Its length is the number of moves applied so far.
+    log: Vec<Vec<char>>,
+}
+
+impl Simulation {
+    pub fn new(crane: Box<dyn Crane>, stacks: Stacks, moves: Vec<Move>) -> Simulation {
+        Simulation { crane: crane, stacks: stacks, moves: moves, log: Vec::new() }
+    }
+
+    pub fn crane(&self) -> &dyn Crane {
+        self.crane.as_ref()
+    }
+
+    // Number of moves applied so far
+    pub fn position(&self) -> usize {
+        self.log.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    // The move that would be applied next
+    pub fn next_move(&self) -> Option<&Move> {
+        self.moves.get(self.position())
+    }
+
+    pub fn remaining_moves(&self) -> &[Move] {
+        &self.moves[self.position()..]
+    }
+
+    // The stacks followed by the moves that are still to be applied, in the puzzle format
+    pub fn render(&self) -> String {
+        let mut out = self.stacks.render();
+        if !self.remaining_moves().is_empty() {
+            out.push('\n');
+            for m in self.remaining_moves() {
+                out.push_str(&m.render(&self.stacks));
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Applies the next move. Returns false if there's none left.
+    pub fn step(&mut self) -> Result<bool, String> {
+        let m = match self.next_move() {
+            Some(m) => *m,
+            None => return Ok(false),
+        };
+        let available = self.stacks.stacks[m.from].len();
+        if available == 0 && m.count > 0 {
+            return Err(format!("Line {}: moving from empty stack {}.", m.line, self.stacks.ids[m.from]));
+        }
+        if available < m.count {
+            return Err(format!("Line {}: moving {} crates from stack {} which only has {}.",
+                               m.line, m.count, self.stacks.ids[m.from], available));
+        }
+
+        let block = self.stacks.stacks[m.from].split_off(available - m.count);
+        if m.from == m.to {
+            // Whatever the crane, putting crates back where they came from changes nothing
+            self.stacks.stacks[m.to].extend(block.iter().copied());
+        } else {
+            self.stacks.stacks[m.to].extend(self.crane.arrange(block.clone()));
+        }
+        self.log.push(block);
+        Ok(true)
+    }
+
+    // Same as step, kept for symmetry with undo
+    pub fn redo(&mut self) -> Result<bool, String> {
+        self.step()
+    }
+
+    // Reverts the last applied move. Returns false if there's nothing to revert.
+    pub fn undo(&mut self) -> bool {
+        let block = match self.log.pop() {
+            Some(block) => block,
+            None => return false,
+        };
+        let m = self.moves[self.log.len()];
+        let to_stack = &mut self.stacks.stacks[m.to];
+        to_stack.truncate(to_stack.len() - block.len());
+        self.stacks.stacks[m.from].extend(block);
+        true
+    }
+
+    // Moves forwards or backwards until exactly `step` moves are applied
+    pub fn seek(&mut self, step: usize) -> Result<(), String> {
+        if step > self.len() {
+            return Err(format!("There are only {} moves.", self.len()));
+        }
+        while self.position() > step {
+            self.undo();
+        }
+        while self.position() < step {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    pub fn run(&mut self) -> Result<(), String> {
+        self.seek(self.len())
+    }
+}
+
+// Shared driver of both parts, which only differ in the default crane.
+// Usage: <file> [--crane 9000|9001|lift:<n>] [--step <n>] [--render | --render-each]
+// --render prints the final drawing (followed by the moves not applied yet) instead of the top
+// crates and --render-each prints the drawing before the first move and after every move.
+pub fn run_cli(default_crane: &str) {
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).expect("No filename provided."));
+    let contents = fs::read_to_string(&path).expect("Couldn't read file.");
+
+    let option = |name: &str| args.iter().position(|x| x == name).map(|i| args.get(i + 1).expect("Missing option value."));
+    let crane = crane_from_name(option("--crane").map_or(default_crane, |x| x.as_str())).expect("Unknown crane.");
+
+    let (stacks, moves) = stacks::parse(&contents).expect("Invalid input.");
+    let mut sim = Simulation::new(crane, stacks, moves);
+    let target = option("--step").map_or(sim.len(), |n| n.parse().expect("Invalid step."));
+    if target > sim.len() {
+        panic!("There are only {} moves.", sim.len());
+    }
+    if args.contains(&String::from("--render-each")) {
+        print!("{}", sim.stacks.render());
+        while sim.position() < target {
+            let m = sim.next_move().unwrap().render(&sim.stacks);
+            if let Err(e) = sim.step() {
+                panic!("{}", e);
+            }
+            print!("\n{m}\n{}", sim.stacks.render());
+        }
+        return;
+    }
+    if let Err(e) = sim.seek(target) {
+        panic!("{}", e);
+    }
+
+    if args.contains(&String::from("--render")) {
+        print!("{}", sim.render());
+    } else {
+        println!("{}", sim.stacks.tops());
+    }
+}
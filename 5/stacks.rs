@@ -0,0 +1,156 @@
+// Parsing of the crate drawing and the rearrangement procedure.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stacks {
+    // Stack ids as written in the footer of the drawing, left to right
+    pub ids: Vec<usize>,
+    // Crates of every stack, bottom first
+    pub stacks: Vec<Vec<char>>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Move {
+    pub count: usize,
+    // Indices into Stacks::stacks, not stack ids
+    pub from: usize,
+    pub to: usize,
+    // 1-based line in the input file
+    pub line: usize,
+}
+
+impl Stacks {
+    // The drawing is a block of `[X]` crates followed by a footer with the stack ids.
+    // Every crate belongs to the stack whose id is written under it.
+    pub fn parse(drawing: &str) -> Result<Stacks, String> {
+        let lines: Vec<&str> = drawing.lines().filter(|x| !x.trim().is_empty()).collect();
+        let (footer, crate_lines) = lines.split_last().ok_or("Empty drawing.")?;
+
+        // (first column, last column, id) of every footer token
+        let mut columns: Vec<(usize, usize, usize)> = Vec::new();
+        let footer: Vec<char> = footer.chars().collect();
+        let mut i = 0;
+        while i < footer.len() {
+            if footer[i].is_whitespace() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < footer.len() && !footer[i].is_whitespace() {
+                i += 1;
+            }
+            let token: String = footer[start..i].iter().collect();
+            let id = token.parse().map_err(|_| format!("Invalid stack id in footer: {token}"))?;
+            columns.push((start, i - 1, id));
+        }
+        if columns.is_empty() {
+            return Err(String::from("No stack ids in footer."));
+        }
+
+        let mut stacks = Stacks {
+            ids: columns.iter().map(|x| x.2).collect(),
+            stacks: vec![Vec::new(); columns.len()],
+        };
+        for (row, line) in crate_lines.iter().enumerate().rev() {
+            let line: Vec<char> = line.chars().collect();
+            let mut seen = vec![false; columns.len()];
+            for (col, ch) in line.iter().enumerate() {
+                if *ch != '[' {
+                    continue;
+                }
+                let label = *line.get(col + 1).ok_or(format!("Line {}: unterminated crate.", row + 1))?;
+                if line.get(col + 2) != Some(&']') {
+                    return Err(format!("Line {}: unterminated crate.", row + 1));
+                }
+                let center = col + 1;
+                let idx = columns.iter()
+                    .position(|(s, e, _)| *s <= center + 1 && center <= e + 1)
+                    .ok_or(format!("Line {}: crate [{label}] isn't above any stack id.", row + 1))?;
+                if seen[idx] {
+                    return Err(format!("Line {}: two crates above stack {}.", row + 1, stacks.ids[idx]));
+                }
+                seen[idx] = true;
+                stacks.stacks[idx].push(label);
+            }
+        }
+        Ok(stacks)
+    }
+
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.ids.iter().position(|x| *x == id)
+    }
+
+    pub fn tops(&self) -> String {
+        self.stacks.iter().map(|s| s.last().copied().unwrap_or(' ')).collect()
+    }
+
+    // Draws the stacks in the same format as the input, footer included, so that the output
+    // parses back to the same stacks. Columns are 4 characters wide unless the ids need more.
+    pub fn render(&self) -> String {
+        let id_width = self.ids.iter().map(|x| x.to_string().len()).max().unwrap_or(1);
+        let width = std::cmp::max(4, id_width + 2);
+        let line_len = width * self.stacks.len() - 1;
+        let height = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for row in (0..height).rev() {
+            let mut line = String::new();
+            for stack in &self.stacks {
+                let cell = match stack.get(row) {
+                    Some(label) => format!("[{label}]"),
+                    None => String::from("   "),
+                };
+                line.push_str(&format!("{:<width$}", cell, width = width));
+            }
+            out.push_str(&line[..line_len]);
+            out.push('\n');
+        }
+        let mut footer = String::new();
+        for id in &self.ids {
+            footer.push_str(&format!(" {:<width$}", id, width = width - 1));
+        }
+        out.push_str(&footer[..line_len]);
+        out.push('\n');
+        out
+    }
+}
+
+impl Move {
+    pub fn render(&self, stacks: &Stacks) -> String {
+        format!("move {} from {} to {}", self.count, stacks.ids[self.from], stacks.ids[self.to])
+    }
+}
+
+// Parses `move N from A to B` lines. `first_line` is the line number of the first one.
+pub fn parse_moves(procedure: &str, stacks: &Stacks, first_line: usize) -> Result<Vec<Move>, String> {
+    let index: HashMap<usize, usize> = stacks.ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut moves = Vec::new();
+    for (i, line) in procedure.lines().enumerate() {
+        let line_no = first_line + i;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            [] => continue,
+            ["move", count, "from", from, "to", to] => {
+                let number = |x: &str| x.parse::<usize>().map_err(|_| format!("Line {line_no}: invalid number {x}."));
+                let stack = |x: &str| {
+                    let id = number(x)?;
+                    index.get(&id).copied().ok_or(format!("Line {line_no}: no stack with id {id}."))
+                };
+                moves.push(Move { count: number(count)?, from: stack(from)?, to: stack(to)?, line: line_no });
+            }
+            _ => return Err(format!("Line {line_no}: expected `move N from A to B`.")),
+        }
+    }
+    Ok(moves)
+}
+
+// Splits the puzzle input into the drawing and the moves, which may be left out
+pub fn parse(contents: &str) -> Result<(Stacks, Vec<Move>), String> {
+    let (drawing, procedure) = contents.split_once("\n\n").unwrap_or((contents, ""));
+    let stacks = Stacks::parse(drawing)?;
+    let first_line = drawing.lines().count() + 2;
+    let moves = parse_moves(procedure, &stacks, first_line)?;
+    Ok((stacks, moves))
+}
@@ -3,27 +3,30 @@ use std::path::Path;
 use std::env;
 use std::io::Read;
 
-fn all_different(slice: &[u8]) -> bool {
-    let mut bit_vec: [u8; 26] = Default::default();
-    for ch in slice {
-        bit_vec[*ch as usize - 97] = 1;
-    }
-    return bit_vec.iter().sum::<u8>() == 4
-}
+mod marker;
 
+const WINDOW_SIZE: usize = 4;
+
+// Usage: main1 <file> [--window <n>] [--all]
 fn main() {
     let args: Vec<String> = env::args().collect();
     let path = Path::new(args.get(1).expect("No filename provided."));
     let mut file = File::open(&path).expect("Couldn't open file.");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Couldn't read file.");
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).expect("Couldn't read file.");
+    while contents.last() == Some(&b'\n') || contents.last() == Some(&b'\r') {
+        contents.pop();
+    }
 
-    let mut i = 0;
-    for slice in contents.as_bytes().windows(4) {
-        if all_different(slice) {
-            break;
+    let window = match args.iter().position(|x| x == "--window") {
+        Some(i) => args.get(i + 1).and_then(|x| x.parse().ok()).expect("Invalid window size."),
+        None => WINDOW_SIZE,
+    };
+    if args.contains(&String::from("--all")) {
+        for pos in marker::all_markers(&contents, window) {
+            println!("{pos}");
         }
-        i += 1;
+    } else {
+        println!("{}", marker::first_marker(&contents, window).expect("No marker found."));
     }
-    println!("{}", i + 4);
 }
@@ -0,0 +1,145 @@
+// Incremental detection of windows of pairwise different bytes.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+pub struct MarkerDetector {
+    window: usize,
+    // The last `window` bytes, as a ring buffer
+    recent: Vec<u8>,
+    counts: [u32; 256],
+    // Number of byte values occurring more than once in the current window
+    duplicates: usize,
+    // Bytes seen so far
+    offset: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(window: usize) -> MarkerDetector {
+        if window == 0 {
+            panic!("Window size must be positive.");
+        }
+        MarkerDetector {
+            window: window,
+            recent: vec![0; window],
+            counts: [0; 256],
+            duplicates: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    // Feeds one byte in O(1). Returns true if the last `window` bytes are all different,
+    // i.e. a marker ends with this byte.
+    pub fn push(&mut self, byte: u8) -> bool {
+        let slot = self.offset % self.window;
+        if self.offset >= self.window {
+            let old = self.recent[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.duplicates -= 1;
+            }
+        }
+        self.recent[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.duplicates += 1;
+        }
+        self.offset += 1;
+
+        self.offset >= self.window && self.duplicates == 0
+    }
+
+    // Number of bytes consumed so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+// Positions (number of bytes processed, as in the puzzle answer) of every marker in data
+pub fn all_markers(data: &[u8], window: usize) -> Vec<usize> {
+    let mut detector = MarkerDetector::new(window);
+    data.iter().filter_map(|b| if detector.push(*b) { Some(detector.offset()) } else { None }).collect()
+}
+
+pub fn first_marker(data: &[u8], window: usize) -> Option<usize> {
+    let mut detector = MarkerDetector::new(window);
+    data.iter().position(|b| detector.push(*b)).map(|i| i + 1)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MarkerKind { Packet, Message }
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Event {
+    pub kind: MarkerKind,
+    // Bytes consumed when the marker was complete, as in the puzzle answer
+    pub offset: usize,
+}
+
+struct Tracker {
+    kind: MarkerKind,
+    detector: MarkerDetector,
+    in_marker: bool,
+}
+
+// Scans a byte stream of unknown length and yields an event whenever a run of distinct-byte
+// windows starts, so a marker is reported once even if the following windows are markers too
+pub struct Framer<R: Read> {
+    source: R,
+    buf: Vec<u8>,
+    trackers: Vec<Tracker>,
+    pending: VecDeque<Event>,
+    done: bool,
+}
+
+impl<R: Read> Framer<R> {
+    pub fn new(source: R, packet_window: usize, message_window: usize) -> Framer<R> {
+        let tracker = |kind, window| Tracker { kind: kind, detector: MarkerDetector::new(window), in_marker: false };
+        Framer {
+            source: source,
+            buf: vec![0; 8192],
+            trackers: vec![tracker(MarkerKind::Packet, packet_window), tracker(MarkerKind::Message, message_window)],
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn feed(&mut self, byte: u8) {
+        for t in self.trackers.iter_mut() {
+            let is_marker = t.detector.push(byte);
+            if is_marker && !t.in_marker {
+                self.pending.push_back(Event { kind: t.kind, offset: t.detector.offset() });
+            }
+            t.in_marker = is_marker;
+        }
+    }
+}
+
+impl<R: Read> Iterator for Framer<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<io::Result<Event>> {
+        while self.pending.is_empty() && !self.done {
+            match self.source.read(&mut self.buf) {
+                Ok(0) => self.done = true,
+                Ok(n) => {
+                    for i in 0..n {
+                        let byte = self.buf[i];
+                        self.feed(byte);
+                    }
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
@@ -0,0 +1,36 @@
+use std::fs::File;
+use std::env;
+use std::io::{self, Read, Write};
+
+mod marker;
+
+use marker::{Framer, MarkerKind};
+
+// Usage: stream [file, stdin if missing or -] [--packet-window <n>] [--message-window <n>]
+// Prints every start-of-packet and start-of-message marker as soon as it's read.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let option = |name: &str, default: usize| match args.iter().position(|x| x == name) {
+        Some(i) => args.get(i + 1).and_then(|x| x.parse().ok()).expect("Invalid window size."),
+        None => default,
+    };
+    let packet_window = option("--packet-window", 4);
+    let message_window = option("--message-window", 14);
+
+    let source: Box<dyn Read> = match args.get(1).filter(|x| !x.starts_with("--")) {
+        Some(path) if path != "-" => Box::new(File::open(path).expect("Couldn't open file.")),
+        _ => Box::new(io::stdin()),
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for event in Framer::new(source, packet_window, message_window) {
+        let event = event.expect("Couldn't read datastream.");
+        let kind = match event.kind {
+            MarkerKind::Packet => "start-of-packet",
+            MarkerKind::Message => "start-of-message",
+        };
+        writeln!(out, "{kind} {}", event.offset).expect("Couldn't write output.");
+        out.flush().expect("Couldn't write output.");
+    }
+}
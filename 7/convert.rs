@@ -0,0 +1,17 @@
+use std::path::Path;
+use std::env;
+
+mod filesystem;
+
+use filesystem::FileSystem;
+
+// Converts between a .json export and a terminal transcript (any other extension)
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let input = Path::new(args.get(1).expect("No input filename provided."));
+    let output = Path::new(args.get(2).expect("No output filename provided."));
+
+    let tree = FileSystem::load(&input).expect("Couldn't load filesystem.");
+    tree.save(&output).expect("Couldn't save filesystem.");
+    println!("Converted {} entries.", tree.nodes.len() - 1);
+}
@@ -0,0 +1,258 @@
+// Filesystem reconstructed from a `$ cd` / `$ ls` terminal transcript.
+#![allow(dead_code)]
+
+#[path = "../common/json.rs"]
+mod json;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use self::json::Json;
+
+pub const ROOT: usize = 0;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum NodeKind {
+    File(u64),
+    // Children by name
+    Dir(BTreeMap<String, usize>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Node {
+    pub name: String,
+    // The root is its own parent
+    pub parent: usize,
+    pub kind: NodeKind,
+}
+
+impl Node {
+    pub fn is_dir(&self) -> bool {
+        match self.kind {
+            NodeKind::Dir(_) => true,
+            NodeKind::File(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileSystem {
+    // Indexed by node id, the root is ROOT
+    pub nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node { name: String::from("/"), parent: ROOT, kind: NodeKind::Dir(BTreeMap::new()) }],
+        }
+    }
+
+    pub fn from_transcript(transcript: &str) -> Result<FileSystem, String> {
+        let mut fs = FileSystem::new();
+        let mut cwd = ROOT;
+        // Directory being listed by the last `$ ls`, if output is allowed
+        let mut listing: Option<usize> = None;
+
+        for (i, line) in transcript.lines().enumerate() {
+            let err = |msg: String| format!("Line {}: {msg}", i + 1);
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(command) = line.strip_prefix("$ ") {
+                listing = None;
+                let words: Vec<&str> = command.split_whitespace().collect();
+                match words[..] {
+                    // The rest of the line, so names may contain spaces
+                    ["cd", _, ..] => cwd = fs.resolve(cwd, command[3..].trim()).map_err(err)?,
+                    ["ls"] => listing = Some(cwd),
+                    _ => return Err(err(format!("unknown command `{command}`."))),
+                }
+            } else {
+                let dir = listing.ok_or_else(|| err(String::from("output outside of `ls`.")))?;
+                let (info, name) = line.split_once(" ").ok_or_else(|| err(String::from("invalid `ls` entry.")))?;
+                let kind = if info == "dir" {
+                    NodeKind::Dir(BTreeMap::new())
+                } else {
+                    NodeKind::File(info.parse().map_err(|_| err(format!("invalid size `{info}`.")))?)
+                };
+                fs.add(dir, name, kind).map_err(err)?;
+            }
+        }
+        Ok(fs)
+    }
+
+    // Reads a .json export or, for any other extension, a terminal transcript
+    pub fn load(path: &Path) -> Result<FileSystem, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("Couldn't read file: {e}"))?;
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("json") => FileSystem::from_json(&Json::parse(&contents)?),
+            _ => FileSystem::from_transcript(&contents),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = match path.extension().and_then(|x| x.to_str()) {
+            Some("json") => self.to_json().pretty() + "\n",
+            _ => self.to_transcript(),
+        };
+        fs::write(path, contents).map_err(|e| format!("Couldn't write file: {e}"))
+    }
+
+    // {"name": "/", "type": "dir", "children": [{"name": "a.txt", "type": "file", "size": 12}, ...]}
+    pub fn to_json(&self) -> Json {
+        self.node_to_json(ROOT)
+    }
+
+    fn node_to_json(&self, idx: usize) -> Json {
+        let node = &self.nodes[idx];
+        let mut fields = vec![(String::from("name"), Json::Str(node.name.clone()))];
+        match &node.kind {
+            NodeKind::File(size) => {
+                fields.push((String::from("type"), Json::Str(String::from("file"))));
+                fields.push((String::from("size"), Json::Number(*size as i64)));
+            }
+            NodeKind::Dir(children) => {
+                fields.push((String::from("type"), Json::Str(String::from("dir"))));
+                let children = children.values().map(|c| self.node_to_json(*c)).collect();
+                fields.push((String::from("children"), Json::Array(children)));
+            }
+        }
+        Json::Object(fields)
+    }
+
+    pub fn from_json(root: &Json) -> Result<FileSystem, String> {
+        if root.get("type").and_then(|x| x.as_str()) != Some("dir") {
+            return Err(String::from("The root must be a directory."));
+        }
+        let mut fs = FileSystem::new();
+        fs.add_json_children(ROOT, root)?;
+        Ok(fs)
+    }
+
+    fn add_json_children(&mut self, dir: usize, value: &Json) -> Result<(), String> {
+        let children = value.get("children").and_then(|x| x.as_array())
+            .ok_or(format!("{} has no \"children\" list.", self.path(dir)))?;
+        for child in children {
+            let name = child.get("name").and_then(|x| x.as_str()).ok_or("Entry without a name.")?;
+            match child.get("type").and_then(|x| x.as_str()) {
+                Some("file") => {
+                    let size = child.get("size").and_then(|x| x.as_i64()).filter(|x| *x >= 0)
+                        .ok_or(format!("File {name} has no valid size."))?;
+                    self.add(dir, name, NodeKind::File(size as u64))?;
+                }
+                Some("dir") => {
+                    let idx = self.add(dir, name, NodeKind::Dir(BTreeMap::new()))?;
+                    self.add_json_children(idx, child)?;
+                }
+                _ => return Err(format!("Entry {name} must have type \"file\" or \"dir\".")),
+            }
+        }
+        Ok(())
+    }
+
+    // A transcript that lists every directory once, depth first, and parses back to this tree
+    pub fn to_transcript(&self) -> String {
+        let mut out = String::from("$ cd /\n");
+        self.write_listing(ROOT, &mut out);
+        out
+    }
+
+    fn write_listing(&self, dir: usize, out: &mut String) {
+        let children = self.children(dir).unwrap();
+        out.push_str("$ ls\n");
+        for (name, idx) in children {
+            match self.nodes[*idx].kind {
+                NodeKind::File(size) => out.push_str(&format!("{size} {name}\n")),
+                NodeKind::Dir(_) => out.push_str(&format!("dir {name}\n")),
+            }
+        }
+        for (name, idx) in children {
+            if self.nodes[*idx].is_dir() {
+                out.push_str(&format!("$ cd {name}\n"));
+                self.write_listing(*idx, out);
+                out.push_str("$ cd ..\n");
+            }
+        }
+    }
+
+    pub fn children(&self, dir: usize) -> Option<&BTreeMap<String, usize>> {
+        match &self.nodes[dir].kind {
+            NodeKind::Dir(children) => Some(children),
+            NodeKind::File(_) => None,
+        }
+    }
+
+    // Adds an entry to a directory. Listing the same entry again is fine, as long as it
+    // doesn't contradict what was listed before.
+    pub fn add(&mut self, dir: usize, name: &str, kind: NodeKind) -> Result<usize, String> {
+        if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+            return Err(format!("invalid name `{name}`."));
+        }
+        let existing = self.children(dir).ok_or(format!("{} is not a directory.", self.path(dir)))?.get(name).copied();
+        if let Some(idx) = existing {
+            return match (&self.nodes[idx].kind, &kind) {
+                (NodeKind::Dir(_), NodeKind::Dir(_)) => Ok(idx),
+                (NodeKind::File(a), NodeKind::File(b)) if a == b => Ok(idx),
+                _ => Err(format!("{} listed twice with different contents.", self.path(idx))),
+            };
+        }
+
+        let idx = self.nodes.len();
+        self.nodes.push(Node { name: String::from(name), parent: dir, kind: kind });
+        if let NodeKind::Dir(children) = &mut self.nodes[dir].kind {
+            children.insert(String::from(name), idx);
+        }
+        Ok(idx)
+    }
+
+    // Resolves an absolute or relative path to a directory
+    pub fn resolve(&self, from: usize, path: &str) -> Result<usize, String> {
+        let mut cur = if path.starts_with('/') { ROOT } else { from };
+        for part in path.split('/') {
+            cur = match part {
+                "" | "." => cur,
+                ".." => self.nodes[cur].parent,
+                _ => match self.children(cur).and_then(|c| c.get(part)) {
+                    Some(idx) if self.nodes[*idx].is_dir() => *idx,
+                    Some(_) => return Err(format!("{path} is not a directory.")),
+                    None => return Err(format!("no such directory: {path}")),
+                },
+            };
+        }
+        Ok(cur)
+    }
+
+    pub fn path(&self, idx: usize) -> String {
+        if idx == ROOT {
+            return String::from("/");
+        }
+        let mut parts = Vec::new();
+        let mut cur = idx;
+        while cur != ROOT {
+            parts.push(self.nodes[cur].name.as_str());
+            cur = self.nodes[cur].parent;
+        }
+        parts.reverse();
+        format!("/{}", parts.join("/"))
+    }
+
+    // Recursive size of every node, indexed by node id
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes = vec![0; self.nodes.len()];
+        self.fill_sizes(ROOT, &mut sizes);
+        sizes
+    }
+
+    fn fill_sizes(&self, idx: usize, sizes: &mut Vec<u64>) -> u64 {
+        sizes[idx] = match &self.nodes[idx].kind {
+            NodeKind::File(size) => *size,
+            NodeKind::Dir(children) => children.values().map(|c| self.fill_sizes(*c, sizes)).sum(),
+        };
+        sizes[idx]
+    }
+
+    pub fn dirs(&self) -> Vec<usize> {
+        (0..self.nodes.len()).filter(|i| self.nodes[*i].is_dir()).collect()
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::env;
+
+mod filesystem;
+
+use filesystem::{FileSystem, ROOT};
+
+// Usage:
+//   report tree <file>
+//   report du <file> [-h]
+//   report find <file> [--name <glob>] [--min-size <n>] [--max-size <n>] [--type f|d]
+//   report plan <file> [--free <n> | --need-unused <n>] [--capacity <n>] [--max-dirs <n>] [--limit <n>]
+
+const CAPACITY: u64 = 70000000;
+const FREE_TARGET: u64 = 30000000;
+
+fn human(size: u64) -> String {
+    let units = ["K", "M", "G", "T"];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value < 10.0 {
+        format!("{:.1}{}", value, units[unit])
+    } else {
+        format!("{:.0}{}", value, units[unit])
+    }
+}
+
+// Shell-style pattern with `*` and `?`
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..])),
+        (Some('?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn print_tree(tree: &FileSystem, sizes: &[u64], idx: usize, depth: usize) {
+    let node = &tree.nodes[idx];
+    let kind = if node.is_dir() { "dir" } else { "file" };
+    println!("{}- {} ({kind}, size={})", "  ".repeat(depth), node.name, sizes[idx]);
+    if let Some(children) = tree.children(idx) {
+        for child in children.values() {
+            print_tree(tree, sizes, *child, depth + 1);
+        }
+    }
+}
+
+fn du(tree: &FileSystem, sizes: &[u64], human_readable: bool) {
+    let mut dirs = tree.dirs();
+    dirs.sort_by(|a, b| sizes[*b].cmp(&sizes[*a]).then_with(|| tree.path(*a).cmp(&tree.path(*b))));
+    for d in dirs {
+        let size = if human_readable { human(sizes[d]) } else { sizes[d].to_string() };
+        println!("{size}\t{}", tree.path(d));
+    }
+}
+
+fn find(tree: &FileSystem, sizes: &[u64], args: &[String]) {
+    let option = |name: &str| args.iter().position(|x| x == name).map(|i| args.get(i + 1).expect("Missing option value."));
+    let size_option = |name: &str| option(name).map(|x| x.parse::<u64>().expect("Invalid size."));
+    let pattern: Option<Vec<char>> = option("--name").map(|x| x.chars().collect());
+    let min_size = size_option("--min-size");
+    let max_size = size_option("--max-size");
+    let want_dirs = match option("--type").map(|x| x.as_str()) {
+        None => None,
+        Some("d") => Some(true),
+        Some("f") => Some(false),
+        Some(_) => panic!("--type must be f or d."),
+    };
+
+    for idx in 0..tree.nodes.len() {
+        let node = &tree.nodes[idx];
+        let name: Vec<char> = node.name.chars().collect();
+        if pattern.as_ref().map_or(true, |p| glob_match(p, &name))
+            && min_size.map_or(true, |s| sizes[idx] >= s)
+            && max_size.map_or(true, |s| sizes[idx] <= s)
+            && want_dirs.map_or(true, |d| d == node.is_dir()) {
+            println!("{}\t{}", sizes[idx], tree.path(idx));
+        }
+    }
+}
+
+fn is_ancestor(tree: &FileSystem, a: usize, mut b: usize) -> bool {
+    while b != ROOT {
+        b = tree.nodes[b].parent;
+        if b == a {
+            return true;
+        }
+    }
+    false
+}
+
+// Collects every set of at most max_dirs directories, none inside another, that frees at
+// least `need` bytes and stops doing so if any of its directories is left out
+fn plan_sets(tree: &FileSystem, sizes: &[u64], dirs: &[usize], need: u64, max_dirs: usize,
+             start: usize, chosen: &mut Vec<usize>, total: u64, out: &mut Vec<(u64, Vec<usize>)>) {
+    if total >= need {
+        if chosen.iter().all(|d| total - sizes[*d] < need) {
+            out.push((total, chosen.clone()));
+        }
+        return;
+    }
+    if chosen.len() == max_dirs {
+        return;
+    }
+    for i in start..dirs.len() {
+        let d = dirs[i];
+        if chosen.iter().any(|c| is_ancestor(tree, *c, d) || is_ancestor(tree, d, *c)) {
+            continue;
+        }
+        chosen.push(d);
+        plan_sets(tree, sizes, dirs, need, max_dirs, i + 1, chosen, total + sizes[d], out);
+        chosen.pop();
+    }
+}
+
+fn plan(tree: &FileSystem, sizes: &[u64], args: &[String]) {
+    let option = |name: &str| args.iter().position(|x| x == name)
+        .map(|i| args.get(i + 1).and_then(|x| x.parse::<u64>().ok()).expect("Invalid numeric option."));
+    let capacity = option("--capacity").unwrap_or(CAPACITY);
+    let need = match option("--free") {
+        Some(n) => n,
+        None => (sizes[ROOT] + option("--need-unused").unwrap_or(FREE_TARGET)).saturating_sub(capacity),
+    };
+    let max_dirs = option("--max-dirs").unwrap_or(3) as usize;
+    let limit = option("--limit").map_or(usize::MAX, |x| x as usize);
+
+    println!("used {}, need to free {need}", sizes[ROOT]);
+    if need == 0 {
+        println!("nothing to delete");
+        return;
+    }
+    // Deleting the root isn't an option
+    let mut dirs: Vec<usize> = tree.dirs().into_iter().filter(|d| *d != ROOT && sizes[*d] > 0).collect();
+    dirs.sort_by(|a, b| sizes[*b].cmp(&sizes[*a]));
+
+    let mut sets = Vec::new();
+    plan_sets(tree, sizes, &dirs, need, max_dirs, 0, &mut Vec::new(), 0, &mut sets);
+    sets.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.len().cmp(&b.1.len())));
+    if sets.is_empty() {
+        println!("no set of up to {max_dirs} directories frees enough space");
+    }
+    for (total, set) in sets.iter().take(limit) {
+        let paths: Vec<String> = set.iter().map(|d| tree.path(*d)).collect();
+        println!("{total}\t{}", paths.join(" "));
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let mode = args.get(1).expect("No mode provided.");
+    let path = Path::new(args.get(2).expect("No filename provided."));
+    // A terminal transcript, or a tree saved as .json
+    let tree = FileSystem::load(&path).expect("Invalid file system.");
+    let sizes = tree.sizes();
+
+    match mode.as_str() {
+        "tree" => print_tree(&tree, &sizes, ROOT, 0),
+        "du" => du(&tree, &sizes, args.contains(&String::from("-h"))),
+        "find" => find(&tree, &sizes, &args[3..]),
+        "plan" => plan(&tree, &sizes, &args[3..]),
+        _ => panic!("Unknown mode, expected tree, du, find or plan."),
+    }
+}
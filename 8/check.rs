@@ -0,0 +1,126 @@
+use std::env;
+
+mod forest;
+
+use forest::Grid;
+
+// Compares the sweeps in forest.rs with the original direct implementations on random forests.
+// Usage: check [number of forests, defaults to 1000] [seed]
+
+type Point = (usize, usize);
+
+// Note: steps below 0 wrap around, which lands outside the grid
+fn point_outside_grid(grid: &Grid, p: Point) -> bool {
+    p.0 >= grid.len() || p.1 >= grid[0].len()
+}
+
+// Original part 1: visibility for the whole grid from one side
+fn one_side_visibility<F, G>(grid: &Grid, start: Point, get_next_row: F, get_next_el: G) -> Grid
+where F: Fn(Point) -> Point, G: Fn(Point) -> Point {
+    let mut output: Grid = vec![vec![0; grid[0].len()]; grid.len()];
+    let mut cur_row_start = start;
+    loop {
+        let mut cur_el = cur_row_start;
+        let mut prev_val = 0;
+        loop {
+            if cur_el == cur_row_start || grid[cur_el.0][cur_el.1] > prev_val {
+                output[cur_el.0][cur_el.1] = 1;
+                prev_val = grid[cur_el.0][cur_el.1];
+            }
+
+            cur_el = get_next_el(cur_el);
+            if point_outside_grid(grid, cur_el) {
+                break;
+            }
+        }
+
+        cur_row_start = get_next_row(cur_row_start);
+        if point_outside_grid(grid, cur_row_start) {
+            break;
+        }
+    }
+    return output;
+}
+
+fn reference_visible(grid: &Grid) -> usize {
+    let out_grids = vec![
+        one_side_visibility(grid, (0, 0), |(x, y)| (x, y.wrapping_add(1)), |(i, j)| (i.wrapping_add(1), j)),
+        one_side_visibility(grid, (0, grid[0].len() - 1), |(x, y)| (x.wrapping_add(1), y), |(i, j)| (i, j.wrapping_sub(1))),
+        one_side_visibility(grid, (grid.len() - 1, 0), |(x, y)| (x, y.wrapping_add(1)), |(i, j)| (i.wrapping_sub(1), j)),
+        one_side_visibility(grid, (0, 0), |(x, y)| (x.wrapping_add(1), y), |(i, j)| (i, j.wrapping_add(1))),
+    ];
+
+    let mut res: Vec<u32> = vec![0; grid.len() * grid[0].len()];
+    for grid in out_grids.iter().map(|g| g.concat()) {
+        for (i, x) in grid.iter().enumerate() {
+            res[i] |= x;
+        }
+    }
+    res.iter().sum::<u32>() as usize
+}
+
+// Original part 2: walk from the tree until the view is blocked
+fn count_one_direction<F>(grid: &Grid, start: Point, get_next_el: F) -> u64
+where F: Fn(Point) -> Point {
+    let mut count = 0;
+    let mut cur_el = start;
+    loop {
+        cur_el = get_next_el(cur_el);
+        if point_outside_grid(grid, cur_el) {
+            break;
+        }
+
+        count += 1;
+        if grid[cur_el.0][cur_el.1] >= grid[start.0][start.1] {
+            break;
+        }
+    }
+
+    return count;
+}
+
+fn reference_score(grid: &Grid, tree: Point) -> u64 {
+    count_one_direction(grid, tree, |(x, y)| (x.wrapping_add(1), y)) *
+    count_one_direction(grid, tree, |(x, y)| (x.wrapping_sub(1), y)) *
+    count_one_direction(grid, tree, |(x, y)| (x, y.wrapping_add(1))) *
+    count_one_direction(grid, tree, |(x, y)| (x, y.wrapping_sub(1)))
+}
+
+// xorshift, good enough to make up forests
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let runs: usize = args.get(1).map_or(1000, |x| x.parse().expect("Invalid number of forests."));
+    let mut state: u64 = args.get(2).map_or(0x2022_0008, |x| x.parse().expect("Invalid seed.")) | 1;
+
+    for run in 0..runs {
+        let rows = 1 + next_random(&mut state) as usize % 30;
+        let cols = 1 + next_random(&mut state) as usize % 30;
+        // Low maximum heights make ties common, which is where off-by-ones hide
+        let max_height = 1 + next_random(&mut state) % 10;
+        let grid: Grid = (0..rows)
+            .map(|_| (0..cols).map(|_| (next_random(&mut state) % max_height) as u32).collect())
+            .collect();
+
+        let visible = forest::visible_count(&grid);
+        if visible != reference_visible(&grid) {
+            panic!("Forest {run}: visible count {visible} != {}", reference_visible(&grid));
+        }
+        let scores = forest::scenic_scores(&grid);
+        for i in 0..rows {
+            for j in 0..cols {
+                let expected = reference_score(&grid, (i, j));
+                if scores[i][j] != expected {
+                    panic!("Forest {run}: scenic score at ({i}, {j}) is {} != {expected}", scores[i][j]);
+                }
+            }
+        }
+    }
+    println!("{runs} forests ok");
+}
@@ -0,0 +1,100 @@
+// Visibility and scenic scores for the whole grid in O(rows * cols), one monotonic stack
+// sweep per row and column in each direction.
+#![allow(dead_code)]
+
+pub type Grid = Vec<Vec<u32>>;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Side { Left, Right, Top, Bottom }
+
+pub const SIDES: [Side; 4] = [Side::Left, Side::Right, Side::Top, Side::Bottom];
+
+// Number of columns, 0 for an empty grid
+pub fn width(grid: &Grid) -> usize {
+    grid.first().map_or(0, |row| row.len())
+}
+
+// For one line of trees seen from its start, returns for every tree
+// (visible from the start, viewing distance towards the start)
+fn sweep_line(heights: &[u32]) -> Vec<(bool, u32)> {
+    let mut res = Vec::with_capacity(heights.len());
+    // Indices of trees not yet blocked by a later tree of at least the same height,
+    // so their heights strictly decrease from bottom to top
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, h) in heights.iter().enumerate() {
+        while let Some(&top) = stack.last() {
+            if heights[top] < *h {
+                stack.pop();
+            } else {
+                break;
+            }
+        }
+        // Whatever is left on the stack is at least as tall, the closest one stops the view
+        let res_i = match stack.last() {
+            None => (true, i as u32),
+            Some(&blocker) => (false, (i - blocker) as u32),
+        };
+        res.push(res_i);
+        stack.push(i);
+    }
+    res
+}
+
+// Indices of the trees in line `k` as seen from `side`, starting at the edge
+fn line_indices(grid: &Grid, side: Side, k: usize) -> Vec<(usize, usize)> {
+    let (rows, cols) = (grid.len(), width(grid));
+    match side {
+        Side::Left => (0..cols).map(|j| (k, j)).collect(),
+        Side::Right => (0..cols).rev().map(|j| (k, j)).collect(),
+        Side::Top => (0..rows).map(|i| (i, k)).collect(),
+        Side::Bottom => (0..rows).rev().map(|i| (i, k)).collect(),
+    }
+}
+
+// For every tree and side: (visible from that side, viewing distance towards that side)
+pub fn sweep(grid: &Grid, side: Side) -> Vec<Vec<(bool, u32)>> {
+    let (rows, cols) = (grid.len(), width(grid));
+    let lines = match side {
+        Side::Left | Side::Right => rows,
+        Side::Top | Side::Bottom => cols,
+    };
+    let mut out = vec![vec![(false, 0); cols]; rows];
+    for k in 0..lines {
+        let idx = line_indices(grid, side, k);
+        let heights: Vec<u32> = idx.iter().map(|(i, j)| grid[*i][*j]).collect();
+        for ((i, j), r) in idx.iter().zip(sweep_line(&heights)) {
+            out[*i][*j] = r;
+        }
+    }
+    out
+}
+
+// Per tree, for each side in SIDES order: (visible from that side, viewing distance towards it)
+pub fn views(grid: &Grid) -> Vec<Vec<[(bool, u32); 4]>> {
+    let sweeps: Vec<Vec<Vec<(bool, u32)>>> = SIDES.iter().map(|s| sweep(grid, *s)).collect();
+    let mut out = vec![vec![[(false, 0); 4]; width(grid)]; grid.len()];
+    for (k, s) in sweeps.iter().enumerate() {
+        for i in 0..grid.len() {
+            for j in 0..width(grid) {
+                out[i][j][k] = s[i][j];
+            }
+        }
+    }
+    out
+}
+
+pub fn visible_count(grid: &Grid) -> usize {
+    views(grid).iter().flatten().filter(|v| v.iter().any(|x| x.0)).count()
+}
+
+pub fn scenic_scores(grid: &Grid) -> Vec<Vec<u64>> {
+    views(grid).iter()
+        .map(|row| row.iter().map(|v| v.iter().map(|x| x.1 as u64).product()).collect())
+        .collect()
+}
+
+pub fn parse(contents: &str) -> Grid {
+    contents.lines().map(|x| x.trim()).filter(|x| !x.is_empty())
+        .map(|line| line.chars().map(|x| x.to_digit(10).expect("Invalid tree height.")).collect())
+        .collect()
+}
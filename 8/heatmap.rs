@@ -0,0 +1,97 @@
+// Grayscale heatmap export: binary PGM, PNG (uncompressed deflate, no dependencies) and CSV.
+#![allow(dead_code)]
+
+// Scales values linearly to 0..=255, the largest one being white
+pub fn to_gray(values: &[Vec<u64>]) -> Vec<Vec<u8>> {
+    let max = values.iter().flatten().copied().max().unwrap_or(0);
+    values.iter()
+        .map(|row| row.iter().map(|x| if max == 0 { 0 } else { (x * 255 / max) as u8 }).collect())
+        .collect()
+}
+
+pub fn pgm(gray: &[Vec<u8>]) -> Vec<u8> {
+    let (height, width) = (gray.len(), gray.first().map_or(0, |r| r.len()));
+    let mut out = format!("P5\n{width} {height}\n255\n").into_bytes();
+    for row in gray {
+        out.extend(row);
+    }
+    out
+}
+
+pub fn csv(values: &[Vec<u64>]) -> String {
+    let mut out = String::new();
+    for row in values {
+        let cells: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xffffffff;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    out.extend(&(data.len() as u32).to_be_bytes());
+    let mut body = kind.to_vec();
+    body.extend(data);
+    out.extend(&body);
+    out.extend(&crc32(&body).to_be_bytes());
+}
+
+// zlib stream made of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(65535).peekable();
+    if blocks.peek().is_none() {
+        out.extend(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let len = block.len() as u16;
+        out.extend(&len.to_le_bytes());
+        out.extend(&(!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(&adler32(data).to_be_bytes());
+    out
+}
+
+pub fn png(gray: &[Vec<u8>]) -> Vec<u8> {
+    let (height, width) = (gray.len() as u32, gray.first().map_or(0, |r| r.len()) as u32);
+    let mut out = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    let mut header = Vec::new();
+    header.extend(&width.to_be_bytes());
+    header.extend(&height.to_be_bytes());
+    // 8-bit grayscale, default compression and filtering, no interlacing
+    header.extend(&[8, 0, 0, 0, 0]);
+    png_chunk(&mut out, b"IHDR", &header);
+
+    // Every scanline starts with filter type 0
+    let mut raw = Vec::new();
+    for row in gray {
+        raw.push(0);
+        raw.extend(row);
+    }
+    png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    png_chunk(&mut out, b"IEND", &[]);
+    out
+}
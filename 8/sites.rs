@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::Path;
+use std::env;
+
+mod forest;
+mod heatmap;
+
+use forest::SIDES;
+
+// Usage: sites <file> [--top <k>] [--visibility] [--pgm <out>] [--png <out>] [--csv <out>]
+// Lists the k best treehouse sites (5 by default) with their viewing distances, optionally
+// prints which sides every tree is visible from and exports the scenic scores.
+
+fn side_name(k: usize) -> &'static str {
+    ["left", "right", "top", "bottom"][k]
+}
+
+// One hex digit per tree with bit k set if the tree is visible from SIDES[k], '.' if hidden
+fn print_visibility(views: &[Vec<[(bool, u32); 4]>]) {
+    println!("visibility (bits: 1 left, 2 right, 4 top, 8 bottom):");
+    for row in views {
+        let line: String = row.iter().map(|v| {
+            let mask = (0..SIDES.len()).filter(|k| v[*k].0).fold(0, |acc, k| acc | (1 << k));
+            if mask == 0 { '.' } else { std::char::from_digit(mask, 16).unwrap() }
+        }).collect();
+        println!("{line}");
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let path = Path::new(args.get(1).expect("No filename provided."));
+    let contents = fs::read_to_string(&path).expect("Couldn't read file.");
+    let option = |name: &str| args.iter().position(|x| x == name).map(|i| args.get(i + 1).expect("Missing option value."));
+    let top: usize = option("--top").map_or(5, |x| x.parse().expect("Invalid number of sites."));
+
+    let grid = forest::parse(&contents);
+    let views = forest::views(&grid);
+    let scores = forest::scenic_scores(&grid);
+
+    let mut sites: Vec<(usize, usize)> = (0..grid.len()).flat_map(|i| (0..forest::width(&grid)).map(move |j| (i, j))).collect();
+    sites.sort_by(|a, b| scores[b.0][b.1].cmp(&scores[a.0][a.1]).then(a.cmp(b)));
+    println!("rank\trow\tcol\theight\tscore\tleft\tright\tup\tdown");
+    for (rank, (i, j)) in sites.iter().take(top).enumerate() {
+        let dists: Vec<String> = views[*i][*j].iter().map(|x| x.1.to_string()).collect();
+        println!("{}\t{i}\t{j}\t{}\t{}\t{}", rank + 1, grid[*i][*j], scores[*i][*j], dists.join("\t"));
+        let visible: Vec<&str> = (0..SIDES.len()).filter(|k| views[*i][*j][*k].0).map(side_name).collect();
+        if !visible.is_empty() {
+            println!("\tvisible from {}", visible.join(", "));
+        }
+    }
+
+    if args.contains(&String::from("--visibility")) {
+        print_visibility(&views);
+    }
+    if let Some(out) = option("--pgm") {
+        fs::write(out, heatmap::pgm(&heatmap::to_gray(&scores))).expect("Couldn't write PGM.");
+    }
+    if let Some(out) = option("--png") {
+        fs::write(out, heatmap::png(&heatmap::to_gray(&scores))).expect("Couldn't write PNG.");
+    }
+    if let Some(out) = option("--csv") {
+        fs::write(out, heatmap::csv(&scores)).expect("Couldn't write CSV.");
+    }
+}
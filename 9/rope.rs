@@ -0,0 +1,108 @@
+// Rope simulation with any number of knots, the head being knot 0. Knots live in 3D; the
+// puzzle's plane is the third coordinate being 0.
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+pub type Point = (i32, i32, i32);
+
+// The head moves by `delta` for `steps` steps
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Motion {
+    pub delta: Point,
+    pub steps: u32,
+}
+
+// A direction is one letter per axis it moves along: U/D, R/L and F/B, so `R` is a straight
+// move, `UR` or `DL` a diagonal one in the plane and `URF` a diagonal one in space
+pub fn parse_direction(dir: &str) -> Option<Point> {
+    let mut delta = [0, 0, 0];
+    for ch in dir.chars() {
+        let (axis, sign) = match ch {
+            'U' => (0, 1),
+            'D' => (0, -1),
+            'R' => (1, 1),
+            'L' => (1, -1),
+            'F' => (2, 1),
+            'B' => (2, -1),
+            _ => return None,
+        };
+        if delta[axis] != 0 {
+            return None;
+        }
+        delta[axis] = sign;
+    }
+    if delta == [0, 0, 0] {
+        return None;
+    }
+    Some((delta[0], delta[1], delta[2]))
+}
+
+pub fn parse_motions(contents: &str) -> Result<Vec<Motion>, String> {
+    let mut motions = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (dir, steps) = line.split_once(" ").ok_or(format!("Line {}: expected `<dir> <steps>`.", i + 1))?;
+        let delta = parse_direction(dir).ok_or(format!("Line {}: unknown direction `{dir}`.", i + 1))?;
+        let steps = steps.trim().parse().map_err(|_| format!("Line {}: couldn't parse the number of steps.", i + 1))?;
+        motions.push(Motion { delta: delta, steps: steps });
+    }
+    Ok(motions)
+}
+
+// New position of a knot following `leader`: if they're no longer touching it moves one
+// step towards the leader along every axis where they differ
+pub fn follow(leader: Point, knot: Point) -> Point {
+    let d = (leader.0 - knot.0, leader.1 - knot.1, leader.2 - knot.2);
+    if d.0.abs() < 2 && d.1.abs() < 2 && d.2.abs() < 2 {
+        knot
+    } else {
+        (knot.0 + d.0.signum(), knot.1 + d.1.signum(), knot.2 + d.2.signum())
+    }
+}
+
+pub struct Rope {
+    pub knots: Vec<Point>,
+    // Set of positions visited by every knot, start included
+    pub visited: Vec<HashSet<Point>>,
+    // Position of every knot after every step, start included
+    pub trails: Vec<Vec<Point>>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize) -> Rope {
+        if knot_count == 0 {
+            panic!("A rope needs at least one knot.");
+        }
+        let start = (0, 0, 0);
+        Rope {
+            knots: vec![start; knot_count],
+            visited: vec![HashSet::from([start]); knot_count],
+            trails: vec![vec![start]; knot_count],
+        }
+    }
+
+    pub fn step(&mut self, delta: Point) {
+        let head = self.knots[0];
+        self.knots[0] = (head.0 + delta.0, head.1 + delta.1, head.2 + delta.2);
+        for i in 1..self.knots.len() {
+            self.knots[i] = follow(self.knots[i - 1], self.knots[i]);
+        }
+        for (i, knot) in self.knots.iter().enumerate() {
+            self.visited[i].insert(*knot);
+            self.trails[i].push(*knot);
+        }
+    }
+
+    pub fn apply(&mut self, motion: &Motion) {
+        for _ in 0..motion.steps {
+            self.step(motion.delta);
+        }
+    }
+
+    pub fn tail(&self) -> usize {
+        self.knots.len() - 1
+    }
+}
@@ -0,0 +1,206 @@
+// Closed integer intervals and sets of them, shared by the days that need interval arithmetic.
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::cmp::{max, min};
+
+// [start, end], both ends included
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Interval(pub i64, pub i64);
+
+impl Interval {
+    pub fn len(&self) -> i64 {
+        max(0, self.1 - self.0 + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.1 < self.0
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.0 <= other.1 && self.1 >= other.0
+    }
+
+    // Checks if other is fully contained in self
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.0 <= other.0 && self.1 >= other.1
+    }
+
+    pub fn contains_point(&self, x: i64) -> bool {
+        self.0 <= x && x <= self.1
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let res = Interval(max(self.0, other.0), min(self.1, other.1));
+        if res.is_empty() { None } else { Some(res) }
+    }
+}
+
+// Disjoint intervals keyed by start. Touching intervals are merged, so two neighbouring
+// entries always have at least one uncovered point between them.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct IntervalSet {
+    map: BTreeMap<i64, i64>,
+    covered: i64,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { map: BTreeMap::new(), covered: 0 }
+    }
+
+    pub fn from_intervals<I: IntoIterator<Item = Interval>>(intervals: I) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for x in intervals {
+            set.insert(x);
+        }
+        set
+    }
+
+    // Number of disjoint intervals
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // Total number of covered points, kept up to date on every change
+    pub fn covered_len(&self) -> i64 {
+        self.covered
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.map.iter().map(|(s, e)| Interval(*s, *e))
+    }
+
+    pub fn first(&self) -> Option<Interval> {
+        self.map.iter().next().map(|(s, e)| Interval(*s, *e))
+    }
+
+    pub fn last(&self) -> Option<Interval> {
+        self.map.iter().next_back().map(|(s, e)| Interval(*s, *e))
+    }
+
+    // The interval of the set starting at or before x, if any
+    fn floor(&self, x: i64) -> Option<Interval> {
+        self.map.range(..=x).next_back().map(|(s, e)| Interval(*s, *e))
+    }
+
+    fn remove_entry(&mut self, x: Interval) {
+        self.map.remove(&x.0);
+        self.covered -= x.len();
+    }
+
+    fn insert_entry(&mut self, x: Interval) {
+        self.map.insert(x.0, x.1);
+        self.covered += x.len();
+    }
+
+    pub fn insert(&mut self, x: Interval) {
+        if x.is_empty() {
+            return;
+        }
+        let mut merged = x;
+        // An interval starting before x can only reach into it if it's the closest one
+        if let Some(prev) = self.floor(x.0) {
+            if prev.1 >= x.0.saturating_sub(1) {
+                merged.0 = prev.0;
+                merged.1 = max(merged.1, prev.1);
+                self.remove_entry(prev);
+            }
+        }
+        let absorbed: Vec<Interval> = self.map.range(merged.0..=merged.1.saturating_add(1))
+            .map(|(s, e)| Interval(*s, *e))
+            .collect();
+        for y in absorbed {
+            merged.1 = max(merged.1, y.1);
+            self.remove_entry(y);
+        }
+        self.insert_entry(merged);
+    }
+
+    pub fn remove(&mut self, x: Interval) {
+        if x.is_empty() {
+            return;
+        }
+        let mut hit: Vec<Interval> = self.map.range(x.0..=x.1).map(|(s, e)| Interval(*s, *e)).collect();
+        if let Some(prev) = self.map.range(..x.0).next_back().map(|(s, e)| Interval(*s, *e)) {
+            if prev.1 >= x.0 {
+                hit.push(prev);
+            }
+        }
+        for y in hit {
+            self.remove_entry(y);
+            if y.0 < x.0 {
+                self.insert_entry(Interval(y.0, x.0 - 1));
+            }
+            if y.1 > x.1 {
+                self.insert_entry(Interval(x.1 + 1, y.1));
+            }
+        }
+    }
+
+    pub fn contains_point(&self, x: i64) -> bool {
+        self.floor(x).map_or(false, |y| y.contains_point(x))
+    }
+
+    // Checks if every point of x is covered
+    pub fn contains(&self, x: &Interval) -> bool {
+        x.is_empty() || self.floor(x.0).map_or(false, |y| y.contains(x))
+    }
+
+    pub fn overlaps(&self, x: &Interval) -> bool {
+        if x.is_empty() {
+            return false;
+        }
+        self.floor(x.1).map_or(false, |y| y.1 >= x.0)
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = self.clone();
+        for x in other.iter() {
+            res.insert(x);
+        }
+        res
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = IntervalSet::new();
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        while let (Some(x), Some(y)) = (a.peek().copied(), b.peek().copied()) {
+            if let Some(z) = x.intersection(&y) {
+                res.insert_entry(z);
+            }
+            if x.1 < y.1 { a.next(); } else { b.next(); }
+        }
+        res
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = self.clone();
+        for x in other.iter() {
+            res.remove(x);
+        }
+        res
+    }
+
+    // Uncovered stretches inside `within`
+    pub fn gaps(&self, within: Interval) -> Vec<Interval> {
+        let mut res = Vec::new();
+        let mut cur = within.0;
+        let start = self.floor(within.0).map_or(within.0, |x| x.0);
+        for (s, e) in self.map.range(start..=within.1) {
+            if *s > cur {
+                res.push(Interval(cur, s - 1));
+            }
+            cur = max(cur, e.saturating_add(1));
+        }
+        if cur <= within.1 {
+            res.push(Interval(cur, within.1));
+        }
+        res
+    }
+}